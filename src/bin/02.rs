@@ -5,7 +5,7 @@ use std::str::FromStr;
 advent_of_code::solution!(2);
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (_, games) = games(input).unwrap();
+    let games = games(input);
 
     let max = Cubes {
         red: 12,
@@ -23,7 +23,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (_, games) = games(input).unwrap();
+    let games = games(input);
 
     Some(
         games
@@ -137,17 +137,18 @@ impl From<&Turn> for Cubes {
 
 mod parse {
     use crate::{Color, Game};
+    use advent_of_code::parse::{lines, parse_all};
     use nom::branch::alt;
     use nom::bytes::complete::tag;
-    use nom::character::complete::{line_ending, space1, u32 as u32_nom};
+    use nom::character::complete::{space1, u32 as u32_nom};
     use nom::combinator::map;
     use nom::multi::separated_list1;
     use nom::sequence::{preceded, separated_pair, tuple};
     use nom::IResult;
     use std::str::FromStr;
 
-    pub fn games(input: &str) -> IResult<&str, Vec<Game>> {
-        separated_list1(line_ending, game)(input)
+    pub fn games(input: &str) -> Vec<Game> {
+        parse_all(lines(game), input).unwrap()
     }
     fn game(input: &str) -> IResult<&str, Game> {
         map(
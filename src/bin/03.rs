@@ -100,7 +100,7 @@ mod parse {
         Symbols(char),
     }
 
-    pub fn schematic(input: &str) -> Schematic {
+    pub fn schematic(input: &str) -> Schematic<'_> {
         let (_, rows) = rows(input).unwrap();
 
         let mut numbers: BTreeSet<NumberString> = BTreeSet::new();
@@ -128,14 +128,14 @@ mod parse {
         Schematic { numbers, symbols }
     }
     // fn map_row(row:usize, Vec<MapEntry>)
-    fn spaces(input: &str) -> IResult<&str, MapEntry> {
+    fn spaces(input: &str) -> IResult<&str, MapEntry<'_>> {
         map(many1_count(complete::char('.')), MapEntry::Spaces)(input)
     }
 
-    fn digits(input: &str) -> IResult<&str, MapEntry> {
+    fn digits(input: &str) -> IResult<&str, MapEntry<'_>> {
         map(digit1, MapEntry::Digits)(input)
     }
-    fn symbol(input: &str) -> IResult<&str, MapEntry> {
+    fn symbol(input: &str) -> IResult<&str, MapEntry<'_>> {
         map(verify(anychar, is_symbol), MapEntry::Symbols)(input)
     }
     fn is_symbol(chr: &char) -> bool {
@@ -144,10 +144,10 @@ mod parse {
             c => !c.is_alphanumeric(),
         }
     }
-    fn entry(input: &str) -> IResult<&str, MapEntry> {
+    fn entry(input: &str) -> IResult<&str, MapEntry<'_>> {
         alt((spaces, digits, symbol))(input)
     }
-    fn rows(input: &str) -> IResult<&str, Vec<Vec<MapEntry>>> {
+    fn rows(input: &str) -> IResult<&str, Vec<Vec<MapEntry<'_>>>> {
         separated_list1(line_ending, many1(entry))(input)
     }
 }
@@ -53,19 +53,17 @@ impl Card {
 
 mod parse {
     use crate::Card;
+    use advent_of_code::parse::{lines, numbers as number_list, parse_all};
     use nom::bytes::complete::tag;
     use nom::character::complete;
-    use nom::character::complete::{char, line_ending, multispace1};
+    use nom::character::complete::{char, multispace1};
     use nom::combinator::map;
-    use nom::multi::separated_list1;
     use nom::sequence::{delimited, preceded, separated_pair, terminated, tuple};
     use nom::IResult;
     use std::collections::BTreeSet;
 
     pub fn load_cards(input: &str) -> Vec<Card> {
-        let (tail, cards) = cards(input.trim()).unwrap();
-        assert_eq!("", tail);
-        cards
+        parse_all(lines(card), input).unwrap()
     }
     fn card_number(input: &str) -> IResult<&str, usize> {
         map(
@@ -77,10 +75,7 @@ mod parse {
         )(input)
     }
     fn numbers(input: &str) -> IResult<&str, BTreeSet<u32>> {
-        map(
-            separated_list1(multispace1, complete::u32),
-            |ns: Vec<u32>| BTreeSet::from_iter(ns),
-        )(input)
+        map(number_list, |ns: Vec<u32>| BTreeSet::from_iter(ns))(input)
     }
     fn card(input: &str) -> IResult<&str, Card> {
         map(
@@ -98,9 +93,6 @@ mod parse {
             },
         )(input)
     }
-    fn cards(input: &str) -> IResult<&str, Vec<Card>> {
-        separated_list1(line_ending, card)(input)
-    }
 }
 #[cfg(test)]
 mod tests {
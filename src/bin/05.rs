@@ -1,117 +1,31 @@
-use crate::parse::read;
+use advent_of_code::intervals::{Interval, IntervalSet, PiecewiseLinear};
 use itertools::Itertools;
-use std::cmp::Ordering;
-use std::collections::VecDeque;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse::read);
 
-pub fn part_one(input: &str) -> Option<i64> {
-    read(input).seed_locations().into_iter().min()
+pub fn part_one(almanac: &Almanac) -> Option<i64> {
+    almanac.seed_locations().into_iter().min()
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let almanac: Almanac = read(input);
+pub fn part_two(almanac: &Almanac) -> Option<i64> {
     almanac
         .translations()
-        .into_iter()
-        .fold(almanac.seed_ranges(), map_ranges)
-        .first()
-        .map(|l| l.start)
+        .iter()
+        .fold(almanac.seed_ranges(), |ranges, layer| {
+            layer.apply_set(&ranges)
+        })
+        .min()
 }
 
-fn map_ranges(ranges: Vec<NumRange>, layer: Vec<Translation>) -> Vec<NumRange> {
-    let mut mapped: Vec<NumRange> = vec![];
-    let mut incoming: VecDeque<NumRange> = VecDeque::from(ranges);
-
-    while let Some(range) = incoming.pop_front() {
-        match layer.iter().find(|t| range.overlaps(&t.range)) {
-            None => mapped.push(range),
-            Some(t) => {
-                if range.start < t.range.start {
-                    mapped.push(NumRange {
-                        start: range.start,
-                        end: t.range.start - 1,
-                    })
-                }
-                mapped.push(t.translate(&range));
-                if range.end > t.range.end {
-                    incoming.push_front(NumRange {
-                        start: t.range.end + 1,
-                        end: range.end,
-                    })
-                }
-            }
-        }
-    }
-    mapped.into_iter().sorted().collect_vec()
-}
-
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
-struct NumRange {
-    start: i64,
-    end: i64,
-}
-impl NumRange {
-    fn contains(&self, num: i64) -> bool {
-        num >= self.start && num <= self.end
-    }
-    fn overlaps(&self, other: &Self) -> bool {
-        self.start <= other.end && self.end >= other.start
-    }
-}
-impl Ord for NumRange {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.start.cmp(&other.start)
-    }
-}
-impl PartialOrd for NumRange {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-#[derive(Debug, Eq, PartialEq, Clone)]
-struct Translation {
-    range: NumRange,
-    delta: i64,
-}
-impl Translation {
-    fn translate(&self, range: &NumRange) -> NumRange {
-        NumRange {
-            start: range.start.max(self.range.start) + self.delta,
-            end: range.end.min(self.range.end) + self.delta,
-        }
-    }
-}
-impl From<Mapping> for Translation {
-    fn from((dst, src, length): Mapping) -> Self {
-        Translation {
-            range: NumRange {
-                start: src,
-                end: src + length - 1,
-            },
-            delta: dst - src,
-        }
-    }
-}
-
-impl PartialOrd<Self> for Translation {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Translation {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.range.start.cmp(&other.range.start)
-    }
+fn translation((dst, src, length): Mapping) -> (Interval<i64>, i64) {
+    (Interval::from_len(src, length).unwrap(), dst - src)
 }
 
 type Mapping = (i64, i64, i64);
 type MappingLayer = Vec<Mapping>;
 type MappingLayers = Vec<MappingLayer>;
 #[derive(Debug)]
-struct Almanac {
+pub struct Almanac {
     seeds: Vec<i64>,
     layers: MappingLayers,
 }
@@ -119,42 +33,25 @@ impl Almanac {
     fn seed_locations(&self) -> Vec<i64> {
         let translations = self.translations();
         self.seeds
-            .clone()
-            .into_iter()
-            .map(|seed| {
-                translations.iter().fold(seed, |acc, layer| {
-                    layer
-                        .iter()
-                        .find_map(|t| match t.range.contains(acc) {
-                            true => Some(acc + t.delta),
-                            false => None,
-                        })
-                        .unwrap_or(acc)
-                })
+            .iter()
+            .map(|&seed| {
+                translations
+                    .iter()
+                    .fold(seed, |acc, layer| layer.apply(acc))
             })
             .collect_vec()
     }
-    fn seed_ranges(&self) -> Vec<NumRange> {
+    fn seed_ranges(&self) -> IntervalSet<i64> {
         self.seeds
             .iter()
             .tuples()
-            .map(|(&start, length)| NumRange {
-                start,
-                end: start + length - 1,
-            })
-            .collect_vec()
+            .filter_map(|(&start, &length)| Interval::from_len(start, length))
+            .collect()
     }
-    fn translations(&self) -> Vec<Vec<Translation>> {
+    fn translations(&self) -> Vec<PiecewiseLinear<i64>> {
         self.layers
             .iter()
-            .map(|layer| {
-                layer
-                    .clone()
-                    .into_iter()
-                    .map(Translation::from)
-                    .sorted()
-                    .collect()
-            })
+            .map(|layer| layer.iter().copied().map(translation).collect())
             .collect_vec()
     }
 }
@@ -221,13 +118,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse::read(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse::read(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(46));
     }
 }
@@ -1,4 +1,5 @@
 use crate::parse::read;
+use advent_of_code::math::quadratic_below;
 use itertools::Itertools;
 use std::str::FromStr;
 
@@ -30,45 +31,13 @@ pub fn part_two(input: &str) -> Option<u64> {
 /**
 Movement function
 f(x) = x * (time - x)
-f(x) = -x² + time*x
 
-Standard form:
-(ax² + bx + c = 0)
-0 = -x² - time*x - record
-x = (-b ± √(b² - 4ac)) / 2a
-
-a = -1
-b = time
-c = -record
+Winning hold times beat the record:
+x * (time - x) > record
+x² - time*x + record < 0
 */
 fn wins(time: u64, record: u64) -> u64 {
-    let (r1, r2) = solve_quadratic(-1, time as i64, -(record as i64));
-
-    let lower = match r1.ceil() as u64 {
-        root if root * (time - root) == record => root + 1,
-        root => root,
-    };
-    let upper = match r2.floor() as u64 {
-        root if root * (time - root) == record => root - 1,
-        root => root,
-    };
-
-    upper - lower + 1
-}
-
-/**
-  Solving via quadratic formula:
-    x = (-b ± √(b² - 4ac)) / 2a
-  No indeterminate roots here.
-*/
-fn solve_quadratic(a: i64, b: i64, c: i64) -> (f64, f64) {
-    let b2minus4ac = b * b - 4 * a * c;
-    let sqrt_b2minus4ac = (b2minus4ac as f64).sqrt();
-    let two_a = 2.0 * a as f64;
-    (
-        (-b as f64 + sqrt_b2minus4ac) / two_a,
-        (-b as f64 - sqrt_b2minus4ac) / two_a,
-    )
+    quadratic_below(1, -(time as i64), record as i64).map_or(0, |holds| holds.size() as u64)
 }
 
 struct Races {
@@ -77,40 +46,29 @@ struct Races {
 }
 mod parse {
     use crate::Races;
-    use nom::bytes::complete::tag;
-    use nom::character::complete::{line_ending, multispace1, u64 as nom_u64};
+    use advent_of_code::parse::{labelled, numbers, parse_all};
+    use nom::character::complete::line_ending;
     use nom::combinator::map;
-    use nom::multi::separated_list1;
-    use nom::sequence::{preceded, separated_pair, terminated};
+    use nom::sequence::separated_pair;
     use nom::IResult;
 
     pub fn read(input: &str) -> Races {
-        let (tail, races) = races(input.trim()).unwrap();
-        assert_eq!("", tail);
-        races
+        parse_all(races, input).unwrap()
     }
 
     fn races(input: &str) -> IResult<&str, Races> {
         map(
-            separated_pair(times, line_ending, distances),
+            separated_pair(
+                labelled("Time", numbers),
+                line_ending,
+                labelled("Distance", numbers),
+            ),
             |(times, distances)| Races {
                 times,
                 records: distances,
             },
         )(input)
     }
-    fn times(input: &str) -> IResult<&str, Vec<u64>> {
-        preceded(
-            terminated(tag("Time:"), multispace1),
-            separated_list1(multispace1, nom_u64),
-        )(input)
-    }
-    fn distances(input: &str) -> IResult<&str, Vec<u64>> {
-        preceded(
-            terminated(tag("Distance:"), multispace1),
-            separated_list1(multispace1, nom_u64),
-        )(input)
-    }
 }
 #[cfg(test)]
 mod tests {
@@ -247,11 +247,11 @@ mod tests {
         assert!(Ace > Two);
         assert_eq!(Ace, Ace);
 
-        for card in vec![Ace, King, Queen, Jack, Ten] {
+        for card in [Ace, King, Queen, Jack, Ten] {
             assert!(Nine < card)
         }
         assert_eq!(Nine, Nine);
-        for card in vec![Eight, Seven, Six, Five, Four, Three, Two, Joker] {
+        for card in [Eight, Seven, Six, Five, Four, Three, Two, Joker] {
             assert!(Nine > card)
         }
     }
@@ -1,6 +1,6 @@
 use crate::parse::read;
+use advent_of_code::math::lcm_all;
 use itertools::Itertools;
-use num::integer::lcm;
 use std::collections::BTreeMap;
 
 advent_of_code::solution!(8);
@@ -56,7 +56,7 @@ pub fn part_two(input: &str) -> Option<u64> {
             })
             .collect_vec();
     }
-    Some(counts.into_iter().reduce(lcm).unwrap() as u64)
+    Some(lcm_all(counts).unwrap() as u64)
 }
 type Node<'a> = (&'a str, (&'a str, &'a str));
 enum Instruction {
@@ -84,12 +84,12 @@ mod parse {
     use nom::sequence::{preceded, separated_pair, terminated, tuple};
     use nom::IResult;
 
-    pub fn read(input: &str) -> Wasteland {
+    pub fn read(input: &str) -> Wasteland<'_> {
         let (tail, wasteland) = wasteland(input.trim()).unwrap();
         assert_eq!("", tail);
         wasteland
     }
-    fn wasteland(input: &str) -> IResult<&str, Wasteland> {
+    fn wasteland(input: &str) -> IResult<&str, Wasteland<'_>> {
         tuple((terminated(instructions, count(line_ending, 2)), nodes))(input)
     }
 
@@ -100,10 +100,10 @@ mod parse {
         map(alt((nom_char('L'), nom_char('R'))), Instruction::from)(input)
     }
 
-    fn nodes(input: &str) -> IResult<&str, Vec<Node>> {
+    fn nodes(input: &str) -> IResult<&str, Vec<Node<'_>>> {
         separated_list1(line_ending, node)(input)
     }
-    fn node(input: &str) -> IResult<&str, Node> {
+    fn node(input: &str) -> IResult<&str, Node<'_>> {
         separated_pair(alphanumeric1, tag(" = "), left_right)(input)
     }
     fn left_right(input: &str) -> IResult<&str, (&str, &str)> {
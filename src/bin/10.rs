@@ -3,6 +3,7 @@ use crate::PipeType::{
     BendNorthEast, BendNorthWest, BendSouthEast, BendSouthWest, Horizontal, Vertical,
 };
 use crate::Tile::{Ground, Pipe, Start};
+use advent_of_code::geometry::interior_points;
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
@@ -13,78 +14,18 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(PipeMaze::from_input(input).loop_length() / 2)
 }
 
+/// Tiles enclosed by the loop
+///
+/// Every tile of the loop is a lattice point on the boundary of the polygon it forms,
+/// so the enclosed tiles are its interior points.
 pub fn part_two(input: &str) -> Option<u64> {
-    let maze = PipeMaze::from_input(input);
-    let loop_map = maze.loop_map();
+    let vertices = PipeMaze::from_input(input)
+        .load_loop()
+        .into_iter()
+        .map(|Point { row, col }| (col as i64, row as i64))
+        .collect_vec();
 
-    Some(
-        maze.tiles
-            .iter()
-            .enumerate()
-            .map(|(row, tiles)| {
-                tiles
-                    .iter()
-                    .enumerate()
-                    .fold(
-                        (0usize, false, None),
-                        |(area, inside, prior), (col, _)| match loop_map.get(&Point { row, col }) {
-                            None => (if inside { area + 1 } else { area }, inside, prior),
-                            Some(tile) => match *tile {
-                                Pipe(Horizontal) => (area, inside, prior),
-                                Pipe(Vertical) => (area, !inside, None),
-                                Pipe(pipe_type) => match (prior, pipe_type) {
-                                    (Some(BendNorthEast), BendSouthWest) => (area, !inside, None),
-                                    (Some(BendSouthEast), BendNorthWest) => (area, !inside, None),
-                                    _ => (area, inside, Some(pipe_type)),
-                                },
-                                _ => panic!("Invalid state"),
-                            },
-                        },
-                    )
-                    .0
-            })
-            .sum::<usize>() as u64,
-    )
-}
-
-fn start_mapping(start: &Point, other: &Point) -> Direction {
-    if start.row < other.row {
-        South
-    } else if start.row > other.row {
-        North
-    } else if start.col > other.col {
-        West
-    } else {
-        East
-    }
-}
-fn determine_starting_pipe(pipe_loop: &[Point]) -> Tile {
-    let start = pipe_loop.first().unwrap();
-    match (
-        start_mapping(start, pipe_loop.get(1).unwrap()),
-        start_mapping(start, pipe_loop.last().unwrap()),
-    ) {
-        (North, South) | (South, North) => Pipe(Vertical),
-        (East, West) | (West, East) => Pipe(Horizontal),
-        (North, East) | (East, North) => Pipe(BendNorthEast),
-        (North, West) | (West, North) => Pipe(BendNorthWest),
-        (South, East) | (East, South) => Pipe(BendSouthEast),
-        (South, West) | (West, South) => Pipe(BendSouthWest),
-        _ => panic!("Invalid combination"),
-    }
-}
-#[derive(Clone)]
-struct PipeCombo(PipeType, PipeType);
-impl From<(PipeType, PipeType)> for PipeCombo {
-    fn from((a, b): (PipeType, PipeType)) -> Self {
-        PipeCombo(a, b)
-    }
-}
-
-impl PartialEq<Self> for PipeCombo {
-    fn eq(&self, other: &Self) -> bool {
-        (self.0 == other.0 || self.0 == other.1) && (self.1 == other.0 || self.1 == other.1)
-    }
+    Some(interior_points(&vertices) as u64)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -245,20 +186,6 @@ impl PipeMaze {
         self.load_loop().len() as u64
     }
 
-    fn loop_map(&self) -> HashMap<Point, Tile> {
-        let pipe_loop = self.load_loop();
-        let start = determine_starting_pipe(&pipe_loop);
-        HashMap::from_iter(pipe_loop.into_iter().map(|p| {
-            (
-                p,
-                match *self.data.get(&p).unwrap() {
-                    Start => start,
-                    tile => tile,
-                },
-            )
-        }))
-    }
-
     fn load_loop(&self) -> Vec<Point> {
         [North, South, East, West]
             .into_iter()
@@ -278,7 +205,7 @@ impl PipeMaze {
         None
     }
 
-    fn iter(&self, direction: Direction) -> PipeWalker {
+    fn iter(&self, direction: Direction) -> PipeWalker<'_> {
         PipeWalker {
             current: self.start,
             direction,
@@ -1,104 +1,245 @@
 use itertools::Itertools;
-use std::cmp::Ordering;
-// use std::cmp::Ordering::*;
-
-advent_of_code::solution!(12);
-
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(
-        input
-            .lines()
-            .map(ConditionRecord::from)
-            .map(|record| record.arrangements())
-            .sum(),
-    )
+use std::fmt::Display;
+use std::str::FromStr;
+
+advent_of_code::solution!(12, debug = debug);
+
+pub fn part_one(input: &str) -> Result<usize, RecordError> {
+    total_arrangements(input, 1)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    Some(
-        input
-            .lines()
-            .map(unfold_condition_record)
-            .map(ConditionRecord::from)
-            .map(|record| record.arrangements())
-            .sum(),
-    )
+pub fn part_two(input: &str) -> Result<usize, RecordError> {
+    total_arrangements(input, 5)
 }
-fn unfold_condition_record(line: &str) -> String {
-    let (s, g) = line.split_once(" ").unwrap();
-    format!(
-        "{} {}",
-        [s, s, s, s, s].join("?"),
-        [g, g, g, g, g].join(",")
-    )
+
+/// Debug output for `cargo solve 12 --debug <line>`: every arrangement of the record on
+/// that line of the input, one per line.
+fn debug(input: &str, request: &str) -> Result<String, String> {
+    let (number, line) = request
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| Some((number, input.lines().nth(number.checked_sub(1)?)?)))
+        .ok_or(format!("no line {request:?} in the input"))?;
+    let record = line
+        .parse::<ConditionRecord>()
+        .map_err(|error| error.on_line(number).to_string())?;
+    Ok(record.arrangements().join("\n"))
 }
 
-struct ConditionRecord {
-    springs: Vec<u8>, // Space efficient
-    groups: Vec<usize>,
+/// The sum of the arrangement counts of every record, each unfolded to `copies` copies.
+fn total_arrangements(input: &str, copies: usize) -> Result<usize, RecordError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse::<ConditionRecord>()
+                .map(|record| record.unfold(copies).count_arrangements())
+                .map_err(|error| error.on_line(i + 1))
+        })
+        .sum()
 }
-impl From<&str> for ConditionRecord {
-    fn from(line: &str) -> Self {
-        let (springs, groups) = line.split_once(' ').unwrap();
-        ConditionRecord {
-            springs: springs.bytes().collect(),
-            groups: groups
-                .split(",")
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect_vec(),
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+impl TryFrom<char> for Spring {
+    type Error = char;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        match ch {
+            '.' => Ok(Spring::Operational),
+            '#' => Ok(Spring::Damaged),
+            '?' => Ok(Spring::Unknown),
+            _ => Err(ch),
         }
     }
 }
-impl From<String> for ConditionRecord {
-    fn from(line: String) -> Self {
-        ConditionRecord::from(line.as_str())
+
+/// Why a line is not a condition record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordError {
+    MissingGroups,
+    InvalidSpring {
+        column: usize,
+        ch: char,
+    },
+    InvalidGroup(String),
+    /// Any of the above, on a given line of the input.
+    OnLine(usize, Box<RecordError>),
+}
+impl RecordError {
+    fn on_line(self, line: usize) -> Self {
+        RecordError::OnLine(line, Box::new(self))
     }
 }
-impl ConditionRecord {
-    fn arrangements(&self) -> usize {
-        let mut dp = vec![vec![None; self.groups.len() + 1]; self.springs.len()];
-        self.count(&mut dp, 0, 0)
+impl Display for RecordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordError::MissingGroups => write!(f, "no damaged group sizes after the springs"),
+            RecordError::InvalidSpring { column, ch } => {
+                write!(f, "invalid spring {ch:?} at column {column}")
+            }
+            RecordError::InvalidGroup(group) => write!(f, "invalid group size {group:?}"),
+            RecordError::OnLine(line, error) => write!(f, "line {line}: {error}"),
+        }
     }
+}
 
-    fn count(&self, dp: &mut Vec<Vec<Option<usize>>>, i: usize, j: usize) -> usize {
-        if i == self.springs.len() {
-            return if j == self.groups.len() { 1 } else { 0 };
+#[derive(Debug, Clone)]
+struct ConditionRecord {
+    springs: Vec<Spring>,
+    groups: Vec<usize>,
+    /// How many springs from each one on could be damaged, so whether a group fits there.
+    could_be_damaged: Vec<usize>,
+}
+impl FromStr for ConditionRecord {
+    type Err = RecordError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (springs, groups) = line.split_once(' ').ok_or(RecordError::MissingGroups)?;
+        Ok(ConditionRecord::new(
+            springs
+                .chars()
+                .enumerate()
+                .map(|(i, ch)| {
+                    Spring::try_from(ch)
+                        .map_err(|ch| RecordError::InvalidSpring { column: i + 1, ch })
+                })
+                .try_collect()?,
+            groups
+                .split(',')
+                .map(|s| {
+                    s.parse()
+                        .map_err(|_| RecordError::InvalidGroup(s.to_string()))
+                })
+                .try_collect()?,
+        ))
+    }
+}
+impl ConditionRecord {
+    fn new(springs: Vec<Spring>, groups: Vec<usize>) -> Self {
+        let mut could_be_damaged = vec![0; springs.len() + 1];
+        for (i, spring) in springs.iter().enumerate().rev() {
+            if *spring != Spring::Operational {
+                could_be_damaged[i] = could_be_damaged[i + 1] + 1;
+            }
         }
-        if let Some(v) = dp[i][j] {
-            return v;
+        ConditionRecord {
+            springs,
+            groups,
+            could_be_damaged,
         }
-        let res = match self.springs[i] {
-            b'.' => self.count(dp, i + 1, j),
-            b'#' => self.count_damaged(dp, i, j),
-            b'?' => self.count(dp, i + 1, j) + self.count_damaged(dp, i, j),
-            _ => panic!("invalid spring!"),
+    }
+
+    /// The record repeated `copies` times, with the springs joined by unknowns.
+    fn unfold(&self, copies: usize) -> Self {
+        let mut springs = vec![];
+        for copy in 0..copies {
+            if copy > 0 {
+                springs.push(Spring::Unknown);
+            }
+            springs.extend(&self.springs);
+        }
+        ConditionRecord::new(springs, self.groups.repeat(copies))
+    }
+
+    fn count_arrangements(&self) -> usize {
+        self.ways()[0][0]
+    }
+
+    /// Every arrangement of operational (`.`) and damaged (`#`) springs that fits the record,
+    /// produced lazily. The table of counts steers the search away from dead ends, so each
+    /// arrangement takes time linear in the number of springs.
+    fn arrangements(&self) -> Arrangements<'_> {
+        let ways = self.ways();
+        let stack = match ways[0][0] > 0 {
+            true => vec![(0, 0, String::new())],
+            false => vec![],
         };
-        dp[i][j] = Some(res);
-        res
+        Arrangements {
+            record: self,
+            ways,
+            stack,
+        }
     }
 
-    fn count_damaged(&self, dp: &mut Vec<Vec<Option<usize>>>, i: usize, j: usize) -> usize {
-        if j == self.groups.len() {
-            return 0;
+    /// `ways[i][j]` is the number of arrangements of the springs from `i` on that hold
+    /// exactly the groups from `j` on.
+    fn ways(&self) -> Vec<Vec<usize>> {
+        let (springs, groups) = (self.springs.len(), self.groups.len());
+        let mut ways = vec![vec![0; groups + 1]; springs + 1];
+        ways[springs][groups] = 1;
+        // `before[j]` springs hold the first `j` groups, each followed by an operational one.
+        // Skip states where those cannot fit before `i`, or the rest cannot fit after it.
+        let before = self.groups.iter().scan(0, |len, group| {
+            *len += group + 1;
+            Some(*len)
+        });
+        let before = std::iter::once(0).chain(before).collect_vec();
+        let total = before[groups];
+        for i in (0..springs).rev() {
+            let fits = |j: usize| before[j] <= i && total - before[j] <= springs - i + 1;
+            for j in (0..=groups).filter(|&j| fits(j)) {
+                if self.springs[i] != Spring::Damaged {
+                    ways[i][j] += ways[i + 1][j];
+                }
+                if let Some(next) = self.place_group(i, j) {
+                    ways[i][j] += ways[next][j + 1];
+                }
+            }
         }
-        let group_end = i + self.groups[j];
-        match group_end.cmp(&self.springs.len()) {
-            Ordering::Greater => 0, // Not enough springs!
-            Ordering::Equal => match self.springs[i..group_end].iter().contains(&b'.') {
-                false if j == self.groups.len() - 1 => 1, // Exact amount and it matches
-                _ => 0,
-            },
-            Ordering::Less => {
-                match !self.springs[i..group_end].iter().contains(&b'.')  // No operational springs in group
-                && self.springs[group_end] != b'#'
-                {
-                    true => self.count(dp, group_end + 1, j + 1),
-                    false => 0,
+        ways
+    }
+
+    /// Where to carry on after group `j` starts at spring `i`, past the operational spring
+    /// that has to follow it, if the group fits there.
+    fn place_group(&self, i: usize, j: usize) -> Option<usize> {
+        let end = i + self.groups.get(j)?;
+        let fits =
+            self.could_be_damaged[i] >= end - i && self.springs.get(end) != Some(&Spring::Damaged);
+        match fits {
+            true => Some((end + 1).min(self.springs.len())),
+            false => None,
+        }
+    }
+}
+
+/// The arrangements of a `ConditionRecord`, in order with `.` before `#`.
+struct Arrangements<'a> {
+    record: &'a ConditionRecord,
+    ways: Vec<Vec<usize>>,
+    /// Partial arrangements of the first `i` springs, holding the first `j` groups.
+    stack: Vec<(usize, usize, String)>,
+}
+impl Iterator for Arrangements<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let springs = &self.record.springs;
+        while let Some((i, j, arrangement)) = self.stack.pop() {
+            if i == springs.len() {
+                return Some(arrangement);
+            }
+            if let Some(next) = self.record.place_group(i, j) {
+                if self.ways[next][j + 1] > 0 {
+                    let mut damaged = arrangement.clone() + &"#".repeat(self.record.groups[j]);
+                    if next > damaged.len() {
+                        damaged.push('.');
+                    }
+                    self.stack.push((next, j + 1, damaged));
                 }
             }
+            if springs[i] != Spring::Damaged && self.ways[i + 1][j] > 0 {
+                self.stack.push((i + 1, j, arrangement + "."));
+            }
         }
+        None
     }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,12 +247,64 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
+        assert_eq!(result, Ok(21));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(525152));
+        assert_eq!(result, Ok(525152));
+    }
+
+    #[test]
+    fn enumerates_arrangements() {
+        let record: ConditionRecord = "?###???????? 3,2,1".parse().unwrap();
+        let arrangements = record.arrangements().collect_vec();
+        assert_eq!(arrangements.len(), record.count_arrangements());
+        assert_eq!(arrangements.first().unwrap(), ".###....##.#");
+        assert_eq!(arrangements.last().unwrap(), ".###.##.#...");
+        assert!(arrangements.iter().all_unique());
+
+        let record: ConditionRecord = "?#?#?#?#?#?#?#? 1,3,1,6".parse().unwrap();
+        assert_eq!(record.arrangements().collect_vec(), vec![".#.###.#.######"]);
+        let record: ConditionRecord = "#.# 2".parse().unwrap();
+        assert_eq!(record.arrangements().next(), None);
+
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(debug(&input, "1"), Ok("#.#.###".to_string()));
+        assert_eq!(debug(&input, "2").unwrap().lines().count(), 4);
+        assert!(debug(&input, "0").is_err());
+    }
+
+    #[test]
+    fn unfolds_any_number_of_copies() {
+        let record: ConditionRecord = ".??..??...?##. 1,1,3".parse().unwrap();
+        let counts = (1..=5)
+            .map(|copies| record.unfold(copies).count_arrangements())
+            .collect_vec();
+        assert_eq!(counts, vec![4, 32, 256, 2048, 16384]);
+        assert_eq!(record.unfold(2).arrangements().count(), 32);
+        assert_eq!(
+            total_arrangements(&advent_of_code::template::read_file("examples", DAY), 2),
+            Ok(1 + 32 + 1 + 2 + 20 + 150)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        assert_eq!(
+            "??x.# 1,1".parse::<ConditionRecord>().unwrap_err(),
+            RecordError::InvalidSpring { column: 3, ch: 'x' }
+        );
+        assert_eq!(
+            total_arrangements("???.### 1,1,3\n.??.. 1,a", 1)
+                .unwrap_err()
+                .to_string(),
+            "line 2: invalid group size \"a\""
+        );
+        assert_eq!(
+            "???".parse::<ConditionRecord>().unwrap_err(),
+            RecordError::MissingGroups
+        );
     }
 }
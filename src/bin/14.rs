@@ -1,81 +1,105 @@
 use crate::Direction::{East, North, South, West};
+use advent_of_code::bits::BitGrid;
+use advent_of_code::cycles::state_at;
 use itertools::Itertools;
-use std::collections::{HashMap, VecDeque};
-use std::hash::Hash;
+use std::fmt::Display;
+use std::ops::Range;
 
 advent_of_code::solution!(14);
 
 pub fn part_one(input: &str) -> Option<usize> {
     let mut platform: Platform = input.into();
     platform.tilt(North);
-    Some(score(platform.data))
+    Some(platform.load())
 }
 pub fn part_two(input: &str) -> Option<usize> {
     let mut platform: Platform = input.into();
-    let mut cache: HashMap<Vec<Vec<char>>, usize> = HashMap::new();
-    let mut rounds: Vec<Vec<Vec<char>>> = vec![];
-    for c in 0..1000000000usize {
-        platform.cycle();
-        let data = platform.data.clone();
-        rounds.push(data.clone());
-        if let Some(existing) = cache.insert(data, c) {
-            let cycle_length = c - existing;
-            let index = (1000000000usize - existing) % cycle_length + (existing - 1);
-            return Some(score(rounds[index].clone()));
-        }
-    }
+    let initial = platform.rounded.clone();
+    let spun = state_at(
+        initial,
+        |rounded| {
+            platform.rounded.clone_from(rounded);
+            platform.cycle();
+            platform.rounded.clone()
+        },
+        1000000000,
+    );
+    platform.rounded = spun.state;
 
-    None
+    Some(platform.load())
 }
 
-#[derive(Hash, Eq, PartialEq)]
+#[derive(Copy, Clone)]
 enum Direction {
     North,
     East,
     South,
     West,
 }
+
+/// A run of cells between cube-shaped rocks or the edge: the row or column it lies in,
+/// and the range of positions along it.
+type Segment = (usize, Range<usize>);
+
 struct Platform {
-    data: Vec<Vec<char>>,
-    mappings: HashMap<Direction, Vec<Vec<(usize, usize)>>>,
+    /// The only part that moves, so also the state to look for cycles in.
+    rounded: BitGrid,
+    cubes: BitGrid,
+    /// Segments running down each column, as rows of the transposed platform, for tilting
+    /// north and south.
+    column_segments: Vec<Segment>,
+    /// Segments running along each row, for tilting east and west.
+    row_segments: Vec<Segment>,
 }
 impl From<&str> for Platform {
     fn from(input: &str) -> Self {
-        let data = input
-            .lines()
-            .map(|line| line.chars().collect_vec())
-            .collect_vec();
-        let size = data.len();
-        let mappings: HashMap<Direction, Vec<Vec<(usize, usize)>>> = HashMap::from([
-            (
-                North,
-                (0..size)
-                    .map(|col| (0..size).map(|row| (row, col)).collect_vec())
-                    .collect_vec(),
-            ),
-            (
-                East,
-                (0..size)
-                    .map(|row| (0..size).rev().map(|col| (row, col)).collect_vec())
-                    .collect_vec(),
-            ),
-            (
-                South,
-                (0..size)
-                    .map(|col| (0..size).rev().map(|row| (row, col)).collect_vec())
-                    .collect_vec(),
-            ),
-            (
-                West,
-                (0..size)
-                    .map(|row| (0..size).map(|col| (row, col)).collect_vec())
-                    .collect_vec(),
-            ),
-        ]);
+        let rounded = BitGrid::parse(input, |ch| ch == 'O');
+        let cubes = BitGrid::parse(input, |ch| ch == '#');
 
-        Platform { data, mappings }
+        Platform {
+            rounded,
+            column_segments: segments(&cubes.transpose()),
+            row_segments: segments(&cubes),
+            cubes,
+        }
     }
 }
+
+/// The segments of each row between the cubes.
+fn segments(cubes: &BitGrid) -> Vec<Segment> {
+    (0..cubes.height())
+        .flat_map(|row| {
+            let walls = (0..cubes.width())
+                .filter(|&col| cubes.contains(row, col))
+                .collect_vec();
+            let starts = std::iter::once(0).chain(walls.iter().map(|wall| wall + 1));
+            let ends = walls.iter().copied().chain(std::iter::once(cubes.width()));
+            starts
+                .zip(ends)
+                .filter(|(start, end)| start < end)
+                .map(|(start, end)| (row, start..end))
+                .collect_vec()
+        })
+        .collect()
+}
+
+/// Moves the rocks in each segment to its start, or its end, by counting and clearing them
+/// with a mask of the segment, then filling a run of as many cells.
+fn settle(rounded: &mut BitGrid, segments: &[Segment], to_start: bool) {
+    for (row, cols) in segments {
+        let rocks = rounded.count_in_row(*row, cols.clone());
+        if rocks == 0 {
+            continue;
+        }
+        rounded.set_in_row(*row, cols.clone(), false);
+        let run = match to_start {
+            true => cols.start..cols.start + rocks,
+            false => cols.end - rocks..cols.end,
+        };
+        rounded.set_in_row(*row, run, true);
+    }
+}
+
 impl Platform {
     fn cycle(&mut self) {
         self.tilt(North);
@@ -83,46 +107,51 @@ impl Platform {
         self.tilt(South);
         self.tilt(East);
     }
+    /// North and south tilt the columns, so they settle rows of the transposed platform.
     fn tilt(&mut self, direction: Direction) {
-        let mapping = self.mappings.get(&direction).unwrap();
-        for group in mapping {
-            let mut spaces: VecDeque<(usize, usize)> = VecDeque::new();
-            for (row, col) in group {
-                let ch = self.data[*row][*col];
-                match ch {
-                    'O' => match spaces.pop_front() {
-                        None => {}
-                        Some((r, c)) => {
-                            self.data[r][c] = 'O';
-                            self.data[*row][*col] = '.';
-                            spaces.push_back((*row, *col));
-                        }
-                    },
-                    '#' => spaces.clear(),
-                    '.' => spaces.push_back((*row, *col)),
-                    _ => panic!("Unexpected character"),
-                }
+        match direction {
+            North | South => {
+                let mut columns = self.rounded.transpose();
+                settle(
+                    &mut columns,
+                    &self.column_segments,
+                    matches!(direction, North),
+                );
+                self.rounded = columns.transpose();
             }
+            East | West => settle(
+                &mut self.rounded,
+                &self.row_segments,
+                matches!(direction, West),
+            ),
         }
     }
+    /// The load on the north support beams.
+    fn load(&self) -> usize {
+        let height = self.rounded.height();
+        self.rounded.iter().map(|(row, _)| height - row).sum()
+    }
 }
 
-fn score(data: Vec<Vec<char>>) -> usize {
-    let size = data.len();
-    data.iter()
-        .enumerate()
-        .flat_map(|(row, line)| {
-            line.iter().filter_map(move |ch| match ch {
-                'O' => Some(size - row),
-                _ => None,
-            })
-        })
-        .sum::<usize>()
-}
-#[allow(dead_code)]
-fn print_platform(data: Vec<Vec<char>>) {
-    let lines: Vec<String> = data.iter().map(|row| row.iter().join("")).collect_vec();
-    println!("{}\n", lines.join("\n"))
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rounded.height() {
+            let line: String = (0..self.rounded.width())
+                .map(|col| {
+                    match (
+                        self.rounded.contains(row, col),
+                        self.cubes.contains(row, col),
+                    ) {
+                        (true, _) => 'O',
+                        (_, true) => '#',
+                        _ => '.',
+                    }
+                })
+                .collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -151,20 +180,22 @@ mod tests {
         ];
 
         platform.cycle();
-        // data = cycle(data.clone());
-        assert_eq!(platform.data, to_data(cycles[0]));
+        assert_eq!(platform.to_string().trim(), cycles[0].trim());
 
         platform.cycle();
-        assert_eq!(platform.data, to_data(cycles[1]));
+        assert_eq!(platform.to_string().trim(), cycles[1].trim());
 
         platform.cycle();
-        assert_eq!(platform.data, to_data(cycles[2]));
+        assert_eq!(platform.to_string().trim(), cycles[2].trim());
     }
 
-    fn to_data(input: &str) -> Vec<Vec<char>> {
-        input
-            .lines()
-            .map(|line| line.chars().collect_vec())
-            .collect_vec()
+    #[test]
+    fn tilts_rectangular_platforms() {
+        let mut platform = Platform::from("O.#O.\n.O..O\nO#.O.");
+        platform.tilt(North);
+        assert_eq!(platform.to_string(), "OO#OO\nO..O.\n.#...\n");
+        platform.tilt(East);
+        assert_eq!(platform.to_string(), "OO#OO\n...OO\n.#...\n");
+        assert_eq!(platform.load(), 4 * 3 + 2 * 2);
     }
 }
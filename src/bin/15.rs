@@ -91,13 +91,13 @@ mod parse {
     use nom::sequence::{preceded, tuple};
     use nom::IResult;
 
-    pub fn read(input: &str) -> Vec<Instruction> {
+    pub fn read(input: &str) -> Vec<Instruction<'_>> {
         instructions(input.trim()).unwrap().1
     }
-    fn instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
+    fn instructions(input: &str) -> IResult<&str, Vec<Instruction<'_>>> {
         separated_list1(complete::char(','), instruction)(input)
     }
-    fn instruction(input: &str) -> IResult<&str, Instruction> {
+    fn instruction(input: &str) -> IResult<&str, Instruction<'_>> {
         tuple((alpha1, operation))(input)
     }
     fn operation(input: &str) -> IResult<&str, Operation> {
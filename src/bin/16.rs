@@ -1,27 +1,29 @@
 use crate::Device::{BackwardMirror, ForwardMirror, HorizontalSplitter, VerticalSplitter};
 use crate::Heading::{Down, Left, Right, Up};
-use itertools::Itertools;
-use std::collections::{BTreeSet, HashMap, VecDeque};
+use advent_of_code::bits::BitGrid;
+use advent_of_code::graph::DirectedGraph;
+use advent_of_code::search::flood_fill;
+use std::collections::HashMap;
 
 advent_of_code::solution!(16);
 
 pub fn part_one(input: &str) -> Option<usize> {
-    Some(Grid::from_input(input).illuminate((0usize, 0usize, Right)))
+    Some(Contraption::from_input(input).illuminate((0, 0, Right)))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let grid = Grid::from_input(input);
-    let last = grid.size - 1;
-    (0..=last)
-        .flat_map(|i| vec![(last, i, Up), (0, i, Down), (i, last, Left), (i, 0, Right)])
-        .map(|start| grid.illuminate(start))
+    let contraption = Contraption::from_input(input);
+    let segments = contraption.segments();
+    contraption
+        .edge_starts()
+        .map(|start| segments.energised(start))
         .max()
 }
 
 type Position = (usize, usize);
 type Light = (usize, usize, Heading);
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Debug)]
+#[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Debug, Hash)]
 enum Heading {
     Up,
     Down,
@@ -53,16 +55,16 @@ impl Device {
         }
     }
 }
-struct Grid {
+struct Contraption {
     devices: HashMap<Position, Device>,
-    size: usize,
+    width: usize,
+    height: usize,
 }
-impl Grid {
+impl Contraption {
     fn from_input(input: &str) -> Self {
         let lines: Vec<&str> = input.trim().lines().collect();
-        let size = lines.len();
-        let width = lines.first().unwrap_or(&"").len();
-        assert_eq!(size, width,);
+        let height = lines.len();
+        let width = lines.first().map_or(0, |line| line.len());
         let devices: HashMap<Position, Device> =
             HashMap::from_iter(lines.into_iter().enumerate().flat_map(|(row, line)| {
                 line.chars()
@@ -76,48 +78,149 @@ impl Grid {
                         _ => None,
                     })
             }));
-        Grid { devices, size }
+        Contraption {
+            devices,
+            width,
+            height,
+        }
+    }
+
+    /// Every beam entering from the edge of the contraption.
+    fn edge_starts(&self) -> impl Iterator<Item = Light> + '_ {
+        let (last_row, last_col) = (self.height - 1, self.width - 1);
+        (0..self.height)
+            .flat_map(move |row| [(row, 0, Right), (row, last_col, Left)])
+            .chain((0..self.width).flat_map(move |col| [(0, col, Down), (last_row, col, Up)]))
+    }
+
+    /// The next tile along the beam, unless it leaves the contraption.
+    fn step(&self, (row, col, heading): Light) -> Option<Light> {
+        let (row, col) = match heading {
+            Up => (row.checked_sub(1)?, col),
+            Down => (row + 1, col),
+            Left => (row, col.checked_sub(1)?),
+            Right => (row, col + 1),
+        };
+        match row < self.height && col < self.width {
+            true => Some((row, col, heading)),
+            false => None,
+        }
+    }
+
+    /// Follows a beam across empty tiles, returning the tiles crossed and the device it hits.
+    fn trace(&self, mut light: Option<Light>) -> (Vec<Position>, Option<Light>) {
+        let mut tiles = vec![];
+        while let Some((row, col, heading)) = light {
+            if self.devices.contains_key(&(row, col)) {
+                return (tiles, light);
+            }
+            tiles.push((row, col));
+            light = self.step((row, col, heading));
+        }
+        (tiles, None)
     }
 
+    /// The number of tiles energised from `start`, following every beam one tile at a time.
+    /// Cheaper than [`Contraption::segments`] for a single start.
     fn illuminate(&self, start: Light) -> usize {
-        let mut word: BTreeSet<Light> = BTreeSet::new();
-        let mut beams: VecDeque<Light> = VecDeque::from(vec![start]);
-
-        while let Some((row, col, heading)) = beams.pop_front() {
-            if word.insert((row, col, heading)) {
-                for beam in self
-                    .devices
-                    .get(&(row, col))
-                    .map(|device| device.operate(heading))
-                    .unwrap_or(vec![heading])
-                    .into_iter()
-                    .map(|heading| match heading {
-                        Up => (row.checked_sub(1), Some(col), heading),
-                        Down => (row.checked_add(1), Some(col), heading),
-                        Left => (Some(row), col.checked_sub(1), heading),
-                        Right => (Some(row), col.checked_add(1), heading),
-                    })
-                    .filter_map(|entry| match entry {
-                        (Some(row), Some(col), heading) if row < self.size && col < self.size => {
-                            Some((row, col, heading))
-                        }
-                        _ => None,
-                    })
-                {
-                    beams.push_back(beam);
+        let mut energised = BitGrid::new(self.width, self.height);
+        for (row, col, _) in flood_fill([start], &|light: &Light| self.beams(light)) {
+            energised.insert(row, col);
+        }
+        energised.len()
+    }
+
+    fn beams(&self, &(row, col, heading): &Light) -> Vec<Light> {
+        self.devices
+            .get(&(row, col))
+            .map_or(vec![heading], |device| device.operate(heading))
+            .into_iter()
+            .filter_map(|heading| self.step((row, col, heading)))
+            .collect()
+    }
+
+    /// Precomputes what every beam hitting a device goes on to energise.
+    ///
+    /// Beams hitting devices form a graph, with an edge to each device the outgoing beams
+    /// hit next. Within a strongly connected group every beam energises the same tiles, and
+    /// the groups come sinks first, so each group's tiles are its own segments plus those of
+    /// groups already done.
+    fn segments(&self) -> Segments<'_> {
+        let mut graph = DirectedGraph::new();
+        let mut own_tiles: HashMap<Light, Vec<Position>> = HashMap::new();
+        for (&(row, col), device) in self.devices.iter() {
+            for heading in [Up, Down, Left, Right] {
+                let light = (row, col, heading);
+                graph.add_node(light);
+                let mut tiles = vec![(row, col)];
+                for out in device.operate(heading) {
+                    let (segment, next) = self.trace(self.step((row, col, out)));
+                    tiles.extend(segment);
+                    if let Some(next) = next {
+                        graph.add_edge(light, next);
+                    }
                 }
+                own_tiles.insert(light, tiles);
             }
         }
-        word.into_iter()
-            .map(|(row, col, _)| (row, col))
-            .unique()
-            .count()
+
+        let mut group_of: HashMap<Light, usize> = HashMap::new();
+        let mut energised: Vec<BitGrid> = vec![];
+        for (index, group) in graph
+            .strongly_connected_components()
+            .into_iter()
+            .enumerate()
+        {
+            group_of.extend(group.iter().map(|light| (*light, index)));
+            let mut tiles = BitGrid::new(self.width, self.height);
+            for light in group.iter() {
+                for &(row, col) in own_tiles[light].iter() {
+                    tiles.insert(row, col);
+                }
+                for next in graph.neighbours(light) {
+                    if group_of[next] != index {
+                        tiles = tiles | &energised[group_of[next]];
+                    }
+                }
+            }
+            energised.push(tiles);
+        }
+
+        Segments {
+            contraption: self,
+            group_of,
+            energised,
+        }
+    }
+}
+
+/// The tiles energised by a beam hitting each device, shared by every strongly connected group.
+struct Segments<'a> {
+    contraption: &'a Contraption,
+    group_of: HashMap<Light, usize>,
+    energised: Vec<BitGrid>,
+}
+
+impl Segments<'_> {
+    /// The number of tiles energised by a beam starting at `start`.
+    fn energised(&self, start: Light) -> usize {
+        let contraption = self.contraption;
+        let (tiles, device) = contraption.trace(Some(start));
+        let mut energised = match device {
+            Some(light) => self.energised[self.group_of[&light]].clone(),
+            None => BitGrid::new(contraption.width, contraption.height),
+        };
+        for (row, col) in tiles {
+            energised.insert(row, col);
+        }
+        energised.len()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use itertools::Itertools;
 
     #[test]
     fn test_part_one() {
@@ -130,4 +233,21 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(51));
     }
+
+    #[test]
+    fn matches_beam_by_beam_on_rectangles() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        for width in [4, 7, 10] {
+            let cropped = input.lines().map(|line| &line[..width]).join("\n");
+            let contraption = Contraption::from_input(&cropped);
+            let segments = contraption.segments();
+            for start in contraption.edge_starts() {
+                assert_eq!(
+                    segments.energised(start),
+                    contraption.illuminate(start),
+                    "{width} wide, from {start:?}"
+                );
+            }
+        }
+    }
 }
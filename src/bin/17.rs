@@ -1,91 +1,165 @@
 use crate::Heading::{Down, Left, Right, Up};
+use advent_of_code::search::dijkstra;
 use itertools::Itertools;
-use pathfinding::directed::astar::astar;
+use std::cmp::Ordering;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(17, debug = debug);
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let crucible = Crucible::from_input(input);
-
-    let end = crucible.end_position();
-
-    vec![Right, Down]
-        .into_iter()
-        .map(|heading| Node {
-            position: Position { row: 0, col: 0 },
-            heading,
-            count: 1,
-        })
-        .filter_map(|start| {
-            astar(
-                &start,
-                |node| crucible.successors(node, 0, 3),
-                |node| (end.row - node.position.row + end.col - node.position.col) * 2,
-                |node| node.position == end,
-            )
-        })
-        // .inspect(|(path, _)| print_path(input, path))
-        .map(|(_path, heat)| heat)
-        .min()
+    City::from_input(input)
+        .least_heat_loss(1, 3)
+        .map(|route| route.heat_loss)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let crucible = Crucible::from_input(input);
-
-    let end = crucible.end_position();
+    City::from_input(input)
+        .least_heat_loss(4, 10)
+        .map(|route| route.heat_loss)
+}
 
-    vec![Right, Down]
-        .into_iter()
-        .map(|heading| Node {
-            position: Position { row: 0, col: 0 },
-            heading,
-            count: 1,
-        })
-        .filter_map(|start| {
-            astar(
-                &start,
-                |node| crucible.successors(node, 4, 10),
-                |node| (end.row - node.position.row + end.col - node.position.col) * 2,
-                |node| node.position == end,
-            )
-        })
-        // .inspect(|(path, _)| print_path(input, path))
-        .map(|(_path, heat)| heat)
-        .min()
+/// Debug output for `cargo solve 17 --debug <request>`: the city with the route of the
+/// `crucible` from part one or the `ultra` crucible from part two drawn over it.
+fn debug(input: &str, request: &str) -> Result<String, String> {
+    let city = City::from_input(input);
+    let (min, max) = match request {
+        "crucible" => (1, 3),
+        "ultra" => (4, 10),
+        _ => {
+            return Err(format!(
+                "unknown request {request:?}, expected crucible or ultra"
+            ))
+        }
+    };
+    city.least_heat_loss(min, max)
+        .map(|route| city.draw_path(&route))
+        .ok_or("no route through the city".to_string())
 }
 
-struct Crucible {
+struct City {
     data: Vec<Vec<usize>>,
+    width: usize,
+    height: usize,
+}
+
+/// Where a crucible is, and along which axis it arrived, since its next move has to turn.
+/// Moving a whole run of blocks at once makes counting the blocks so far unnecessary.
+type State = (Position, Axis);
+
+/// The cheapest way through the city, with every block entered and the heading it was entered with.
+struct Route {
+    heat_loss: usize,
+    path: Vec<(Position, Heading)>,
 }
-impl Crucible {
+
+impl City {
     fn from_input(input: &str) -> Self {
-        Crucible {
-            data: input
-                .trim()
-                .lines()
-                .map(|line| {
-                    line.chars()
-                        .map(|c| c.to_digit(10).unwrap() as usize)
-                        .collect_vec()
-                })
-                .collect_vec(),
+        let data = input
+            .trim()
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .map(|c| c.to_digit(10).unwrap() as usize)
+                    .collect_vec()
+            })
+            .collect_vec();
+        let height = data.len();
+        let width = data.first().map_or(0, Vec::len);
+        City {
+            data,
+            width,
+            height,
         }
     }
-    fn end_position(&self) -> Position {
-        Position::from((self.data.len() - 1, self.data.len() - 1))
+
+    /// The bottom-right block, if the city has any blocks.
+    fn end_position(&self) -> Option<Position> {
+        Some(Position::from((
+            self.height.checked_sub(1)?,
+            self.width.checked_sub(1)?,
+        )))
     }
+
     fn heat(&self, position: Position) -> usize {
         self.data[position.row][position.col]
     }
-    fn successors(&self, node: &Node, min: usize, max: usize) -> Vec<(Node, usize)> {
-        let bounds = self.data.len() - 1;
-        node.successors(bounds)
-            .into_iter()
-            .filter(|n| match n.heading == node.heading {
-                true => node.count < max,
-                false => node.count >= min,
+
+    fn step(&self, position: Position, heading: Heading) -> Option<Position> {
+        position
+            .step(heading)
+            .filter(|p| p.row < self.height && p.col < self.width)
+    }
+
+    /// Every run of `min..=max` blocks in a straight line after turning, with its heat loss.
+    fn moves(&self, &(position, axis): &State, min: usize, max: usize) -> Vec<(State, usize)> {
+        let headings = match axis {
+            Axis::Horizontal => [Up, Down],
+            Axis::Vertical => [Left, Right],
+        };
+        let mut moves = vec![];
+        for heading in headings {
+            let mut current = position;
+            let mut heat = 0;
+            for steps in 1..=max {
+                let Some(next) = self.step(current, heading) else {
+                    break;
+                };
+                current = next;
+                heat += self.heat(current);
+                if steps >= min {
+                    moves.push(((current, heading.axis()), heat));
+                }
+            }
+        }
+        moves
+    }
+
+    /// The route to the bottom-right block losing the least heat, for a crucible that moves
+    /// `min..=max` blocks in a straight line between turns.
+    fn least_heat_loss(&self, min: usize, max: usize) -> Option<Route> {
+        let start = Position::from((0, 0));
+        let end = self.end_position()?;
+        let explored = dijkstra(
+            [(start, Axis::Horizontal), (start, Axis::Vertical)],
+            &|state: &State| self.moves(state, min, max),
+            |(position, _)| *position == end,
+        );
+
+        let mut path = vec![];
+        for ((from, _), (to, _)) in explored.goal_path()?.into_iter().tuple_windows() {
+            let heading = from.heading_to(to);
+            let mut current = from;
+            while current != to {
+                current = self.step(current, heading)?;
+                path.push((current, heading));
+            }
+        }
+        Some(Route {
+            heat_loss: explored.goal_cost()?,
+            path,
+        })
+    }
+
+    /// The city with the route drawn over it, like the puzzle's own examples.
+    fn draw_path(&self, route: &Route) -> String {
+        let mut grid = self
+            .data
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|heat| char::from_digit(*heat as u32, 10).unwrap())
+                    .collect_vec()
             })
-            .map(|node| (node, self.heat(node.position)))
+            .collect_vec();
+        for (position, heading) in route.path.iter() {
+            grid[position.row][position.col] = match heading {
+                Up => '^',
+                Down => 'v',
+                Left => '<',
+                Right => '>',
+            }
+        }
+        grid.into_iter()
+            .map(|line| line.into_iter().collect::<String>() + "\n")
             .collect()
     }
 }
@@ -96,36 +170,7 @@ struct Position {
     col: usize,
 }
 impl Position {
-    fn in_bounds(&self, max: usize) -> bool {
-        self.row <= max && self.col <= max
-    }
-    fn right(&self, heading: Heading) -> Option<Position> {
-        let (row, column) = match heading {
-            Up => (Some(self.row), self.col.checked_add(1)),
-            Down => (Some(self.row), self.col.checked_sub(1)),
-            Left => (self.row.checked_sub(1), Some(self.col)),
-            Right => (self.row.checked_add(1), Some(self.col)),
-        };
-        match (row, column) {
-            (Some(row), Some(col)) => Some(Position { row, col }),
-            _ => None,
-        }
-    }
-
-    fn left(&self, heading: Heading) -> Option<Position> {
-        let (row, column) = match heading {
-            Up => (Some(self.row), self.col.checked_sub(1)),
-            Down => (Some(self.row), self.col.checked_add(1)),
-            Left => (self.row.checked_add(1), Some(self.col)),
-            Right => (self.row.checked_sub(1), Some(self.col)),
-        };
-        match (row, column) {
-            (Some(row), Some(col)) => Some(Position { row, col }),
-            _ => None,
-        }
-    }
-
-    fn straight(&self, heading: Heading) -> Option<Position> {
+    fn step(&self, heading: Heading) -> Option<Position> {
         let (row, column) = match heading {
             Up => (self.row.checked_sub(1), Some(self.col)),
             Down => (self.row.checked_add(1), Some(self.col)),
@@ -137,6 +182,16 @@ impl Position {
             _ => None,
         }
     }
+
+    /// The heading from here to `other`, which must be in the same row or column.
+    fn heading_to(&self, other: Position) -> Heading {
+        match (other.row.cmp(&self.row), other.col.cmp(&self.col)) {
+            (Ordering::Less, _) => Up,
+            (Ordering::Greater, _) => Down,
+            (_, Ordering::Less) => Left,
+            _ => Right,
+        }
+    }
 }
 
 impl From<(usize, usize)> for Position {
@@ -144,45 +199,11 @@ impl From<(usize, usize)> for Position {
         Self { row, col }
     }
 }
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
-struct Node {
-    position: Position,
-    heading: Heading,
-    count: usize,
-}
-
-impl Node {
-    fn successors(&self, bounds: usize) -> Vec<Node> {
-        vec![self.turn_right(), self.turn_left(), self.straight()]
-            .into_iter()
-            .filter_map(|node| match node {
-                Some(node) if node.position.in_bounds(bounds) => Some(node),
-                _ => None,
-            })
-            .collect_vec()
-    }
-    fn turn_right(&self) -> Option<Node> {
-        self.position.right(self.heading).map(|position| Node {
-            position,
-            heading: self.heading.right(),
-            count: 1,
-        })
-    }
 
-    fn turn_left(&self) -> Option<Node> {
-        self.position.left(self.heading).map(|position| Node {
-            position,
-            heading: self.heading.left(),
-            count: 1,
-        })
-    }
-    fn straight(&self) -> Option<Node> {
-        self.position.straight(self.heading).map(|position| Node {
-            position,
-            heading: self.heading,
-            count: self.count + 1,
-        })
-    }
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+enum Axis {
+    Horizontal,
+    Vertical,
 }
 
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
@@ -194,43 +215,14 @@ enum Heading {
 }
 
 impl Heading {
-    fn right(&self) -> Heading {
-        match self {
-            Up => Right,
-            Down => Left,
-            Left => Up,
-            Right => Down,
-        }
-    }
-    fn left(&self) -> Heading {
+    fn axis(&self) -> Axis {
         match self {
-            Up => Left,
-            Down => Right,
-            Left => Down,
-            Right => Up,
+            Up | Down => Axis::Vertical,
+            Left | Right => Axis::Horizontal,
         }
     }
 }
 
-#[allow(dead_code)]
-fn print_path(input: &str, path: &Vec<Node>) {
-    let mut grid = input
-        .trim()
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec();
-    for node in path {
-        grid[node.position.row][node.position.col] = match node.heading {
-            Up => '^',
-            Down => 'v',
-            Left => '<',
-            Right => '>',
-        }
-    }
-    for line in grid {
-        println!("{}", line.into_iter().collect::<String>());
-    }
-}
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,4 +238,37 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(94));
     }
+
+    #[test]
+    fn test_part_two_unfortunate() {
+        let input = "111111111111\n999999999991\n999999999991\n999999999991\n999999999991";
+        assert_eq!(part_two(input), Some(71));
+    }
+
+    #[test]
+    fn finds_no_route_through_an_empty_city() {
+        assert_eq!(part_one(""), None);
+        assert_eq!(part_two("\n"), None);
+    }
+
+    #[test]
+    fn returns_the_path() {
+        let city = City::from_input(&advent_of_code::template::read_file("examples", DAY));
+        let route = city.least_heat_loss(1, 3).unwrap();
+        let heat: usize = route.path.iter().map(|(p, _)| city.heat(*p)).sum();
+        assert_eq!(heat, route.heat_loss);
+        assert_eq!(route.path.last().map(|(p, _)| *p), city.end_position());
+
+        let drawn = debug(
+            &advent_of_code::template::read_file("examples", DAY),
+            "crucible",
+        );
+        assert_eq!(drawn, Ok(city.draw_path(&route)));
+        let drawn = drawn.unwrap();
+        assert_eq!(drawn.lines().count(), 13);
+        assert_eq!(
+            drawn.matches(['^', 'v', '<', '>']).count(),
+            route.path.len()
+        );
+    }
 }
@@ -1,7 +1,6 @@
 use crate::parse::{part1_instruction, part2_instruction};
 use crate::Direction::{Down, Left, Right, Up};
-use itertools::Itertools;
-use std::ops::{Add, Div};
+use advent_of_code::geometry::lattice_points;
 
 advent_of_code::solution!(18);
 
@@ -14,15 +13,10 @@ pub fn part_two(input: &str) -> Option<isize> {
     Some(calculate_area(part2_instructions(input)))
 }
 
-/// Shoelace + extra area
+/// Lattice points of the dug-out lagoon
 ///
-/// The area is calculated from the polygon points
-/// https://en.wikipedia.org/wiki/Shoelace_formula#Exterior_Algebra
-///
-/// This area is measured from the center of the trenches, thus missing half the perimeter blocks
-///  Plus one additional ¼ for the four outer corners unaccounted for in the original area.
-///
-///     extra_area = (perimeter / 2) + 1)
+/// The trench follows the lattice points of the polygon through the centers of its blocks,
+/// so the lagoon covers both the interior and the boundary points of that polygon.
 fn calculate_area(instructions: Vec<Instruction>) -> isize {
     let points: Vec<(isize, isize)> =
         instructions
@@ -37,30 +31,9 @@ fn calculate_area(instructions: Vec<Instruction>) -> isize {
                 });
                 points
             });
-    let perimeter = instructions.iter().map(|(_, steps)| *steps).sum::<isize>();
-    shoelace(&points) + perimeter.div(2).add(1)
+    lattice_points(&points)
 }
 
-/// Shoelace Area
-///
-/// Requires the points to be in consecutive order
-/// https://en.wikipedia.org/wiki/Shoelace_formula#Exterior_Algebra
-///
-///     2A = ∑ ( x * yᵢ₊₁ - y * xᵢ₊₁ )
-///
-/// or ...
-///
-///     A = ( ∑ ( x * yᵢ₊₁ - y * xᵢ₊₁ ) ) / 2
-fn shoelace(points: &[(isize, isize)]) -> isize {
-    points
-        .iter()
-        .circular_tuple_windows()
-        .fold(0isize, |area, ((x1, y1), (x2, y2))| {
-            area + ((x1 * y2) - (x2 * y1))
-        })
-        .abs()
-        .div(2)
-}
 fn part1_instructions(input: &str) -> Vec<Instruction> {
     input
         .lines()
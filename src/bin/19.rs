@@ -1,50 +1,38 @@
-use crate::parse::{parse, workflows};
 use crate::Category::{A, M, S, X};
 use crate::Comparator::{LessThan, MoreThan};
+use advent_of_code::intervals::{Interval, IntervalBox};
 use std::collections::{HashMap, VecDeque};
-use std::ops::RangeInclusive;
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(19, parse::system);
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (workflows, parts) = parse(input);
-    let flows: HashMap<String, Workflow> = HashMap::from_iter(
-        workflows
-            .into_iter()
-            .map(|workflow| (workflow.name.clone(), workflow)),
-    );
+pub fn part_one(system: &System) -> Option<usize> {
     Some(
-        parts
-            .into_iter()
-            .filter(|part| process(part, "in", &flows) == Destination::Accepted)
+        system
+            .parts
+            .iter()
+            .filter(|part| process(part, "in", &system.workflows) == Destination::Accepted)
             .map(|part| part.total())
             .sum(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let flows: HashMap<String, Workflow> = HashMap::from_iter(
-        workflows(input)
-            .unwrap()
-            .1
-            .into_iter()
-            .map(|workflow| (workflow.name.clone(), workflow)),
-    );
+pub fn part_two(system: &System) -> Option<usize> {
+    let flows = &system.workflows;
     let mut queue = VecDeque::from([(
         Destination::Workflow("in".to_string()),
-        RatingRange::new(1..=4000),
+        IntervalBox::new([Interval::new(1, 4000).unwrap(); 4]),
     )]);
     let mut accepted: Vec<RatingRange> = vec![];
 
     while let Some((dest, ranges)) = queue.pop_front() {
         if let Destination::Workflow(name) = dest {
             let workflow: &Workflow = flows.get(&name).unwrap();
-            let mut last = Some(ranges.clone());
+            let mut last = Some(ranges);
             for rule in workflow.rules.iter() {
                 if let Some(ref range) = last {
                     match rule {
                         Rule::Compare(op, dest) => {
-                            let (left, right) = op.split_ranges(range.clone());
+                            let (left, right) = op.split_ranges(*range);
                             if let Some(r) = left {
                                 match dest {
                                     Destination::Accepted => {
@@ -56,10 +44,8 @@ pub fn part_two(input: &str) -> Option<usize> {
                             last = right;
                         }
                         Rule::Destination(dest) => match dest {
-                            Destination::Workflow(_) => {
-                                queue.push_back((dest.clone(), range.clone()))
-                            }
-                            Destination::Accepted => accepted.push(range.clone()),
+                            Destination::Workflow(_) => queue.push_back((dest.clone(), *range)),
+                            Destination::Accepted => accepted.push(*range),
                             Destination::Rejected => {}
                         },
                     }
@@ -67,7 +53,25 @@ pub fn part_two(input: &str) -> Option<usize> {
             }
         }
     }
-    Some(accepted.iter().map(RatingRange::combinations).sum())
+    Some(accepted.iter().map(RatingRange::volume).sum())
+}
+
+/// The workflows (keyed by name) and parts listed in the puzzle input.
+pub struct System {
+    workflows: HashMap<String, Workflow>,
+    parts: Vec<Part>,
+}
+impl From<(Vec<Workflow>, Vec<Part>)> for System {
+    fn from((workflows, parts): (Vec<Workflow>, Vec<Part>)) -> Self {
+        System {
+            workflows: HashMap::from_iter(
+                workflows
+                    .into_iter()
+                    .map(|workflow| (workflow.name.clone(), workflow)),
+            ),
+            parts,
+        }
+    }
 }
 
 fn process(part: &Part, name: &str, workflows: &HashMap<String, Workflow>) -> Destination {
@@ -111,10 +115,15 @@ impl From<char> for Comparator {
         }
     }
 }
-fn rating_range(start: usize, end: usize) -> Option<RangeInclusive<usize>> {
-    match start <= end {
-        true => Some(start..=end),
-        false => None,
+impl Category {
+    /// The axis of the category in a [`RatingRange`].
+    fn axis(&self) -> usize {
+        match self {
+            X => 0,
+            M => 1,
+            A => 2,
+            S => 3,
+        }
     }
 }
 
@@ -192,23 +201,16 @@ impl Comparison {
             .evaluate(part.rating(&self.category), self.value)
     }
 
+    /// Splits ranges into the part matching the comparison and the part that does not.
     fn split_ranges(&self, ranges: RatingRange) -> (Option<RatingRange>, Option<RatingRange>) {
-        let range = ranges.category_range(&self.category);
-        let (left, right) = match self.comparator {
-            LessThan => (
-                rating_range(*range.start(), self.value - 1),
-                rating_range(self.value, *range.end()),
-            ),
-            MoreThan => (
-                rating_range(self.value + 1, *range.end()),
-                rating_range(*range.start(), self.value),
-            ),
-        };
-
-        (
-            left.map(|left| ranges.update(&self.category, left)),
-            right.map(|right| ranges.update(&self.category, right)),
-        )
+        let axis = self.category.axis();
+        match self.comparator {
+            LessThan => ranges.split_at(axis, self.value),
+            MoreThan => {
+                let (below, above) = ranges.split_at(axis, self.value + 1);
+                (above, below)
+            }
+        }
     }
 }
 
@@ -259,71 +261,11 @@ impl Part {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
-struct RatingRange {
-    x: RangeInclusive<usize>,
-    m: RangeInclusive<usize>,
-    a: RangeInclusive<usize>,
-    s: RangeInclusive<usize>,
-}
-
-impl RatingRange {
-    fn new(range: RangeInclusive<usize>) -> Self {
-        RatingRange {
-            x: range.clone(),
-            m: range.clone(),
-            a: range.clone(),
-            s: range,
-        }
-    }
+/// Ranges of ratings for the categories `x`, `m`, `a` and `s`.
+type RatingRange = IntervalBox<usize, 4>;
 
-    fn category_range(&self, category: &Category) -> RangeInclusive<usize> {
-        match category {
-            X => self.x.clone(),
-            M => self.m.clone(),
-            A => self.a.clone(),
-            S => self.s.clone(),
-        }
-    }
-
-    fn update(&self, category: &Category, range: RangeInclusive<usize>) -> RatingRange {
-        match category {
-            X => RatingRange {
-                x: range,
-                m: self.m.clone(),
-                a: self.a.clone(),
-                s: self.s.clone(),
-            },
-            M => RatingRange {
-                x: self.x.clone(),
-                m: range,
-                a: self.a.clone(),
-                s: self.s.clone(),
-            },
-            A => RatingRange {
-                x: self.x.clone(),
-                m: self.m.clone(),
-                a: range,
-                s: self.s.clone(),
-            },
-            S => RatingRange {
-                x: self.x.clone(),
-                m: self.m.clone(),
-                a: self.a.clone(),
-                s: range,
-            },
-        }
-    }
-
-    fn combinations(&self) -> usize {
-        (self.x.end() - self.x.start() + 1)
-            * (self.m.end() - self.m.start() + 1)
-            * (self.a.end() - self.a.start() + 1)
-            * (self.s.end() - self.s.start() + 1)
-    }
-}
 mod parse {
-    use crate::{Category, Comparator, Comparison, Destination, Part, Rule, Workflow};
+    use crate::{Category, Comparator, Comparison, Destination, Part, Rule, System, Workflow};
     use nom::branch::alt;
     use nom::bytes::complete::tag;
     use nom::character::complete;
@@ -333,11 +275,11 @@ mod parse {
     use nom::sequence::{delimited, preceded, separated_pair, tuple};
     use nom::IResult;
 
-    pub fn parse(input: &str) -> (Vec<Workflow>, Vec<Part>) {
+    pub fn system(input: &str) -> System {
         let (tail, workflows) = workflows(input).unwrap();
-        (workflows, parts(tail.trim()).unwrap().1)
+        System::from((workflows, parts(tail.trim()).unwrap().1))
     }
-    pub fn workflows(input: &str) -> IResult<&str, Vec<Workflow>> {
+    fn workflows(input: &str) -> IResult<&str, Vec<Workflow>> {
         separated_list1(line_ending, workflow)(input)
     }
     fn workflow(input: &str) -> IResult<&str, Workflow> {
@@ -380,7 +322,7 @@ mod parse {
     fn category(input: &str) -> IResult<&str, Category> {
         map(one_of("xmas"), Category::from)(input)
     }
-    pub fn parts(input: &str) -> IResult<&str, Vec<Part>> {
+    fn parts(input: &str) -> IResult<&str, Vec<Part>> {
         separated_list1(line_ending, part)(input)
     }
     fn part(input: &str) -> IResult<&str, Part> {
@@ -405,13 +347,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse::system(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(19114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse::system(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(167409079868000));
     }
 }
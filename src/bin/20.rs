@@ -1,242 +1,251 @@
 use crate::parse::parse;
 use crate::Module::{Broadcaster, Conjunction, FlipFlop};
-use std::collections::{BTreeSet, HashMap, VecDeque};
-use std::ops::Sub;
+use advent_of_code::graph::DirectedGraph;
+use advent_of_code::math::lcm_all;
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fmt::Display;
 use std::vec;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, debug = debug);
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let modules: HashMap<String, Module> =
-        HashMap::from_iter(parse(input).into_iter().map(|module| match module.clone() {
-            Broadcaster(output) => ("broadcaster".to_string(), Broadcaster(output)),
-            FlipFlop(name, outputs) => (name.clone(), FlipFlop(name, outputs)),
-            Conjunction(name, outputs) => (name.clone(), Conjunction(name, outputs)),
-        }));
-    let upstream: HashMap<String, Vec<String>> =
-        modules.iter().fold(HashMap::new(), |mut m, (_, module)| {
-            let (name, outputs) = match module {
-                Broadcaster(outputs) => ("broadcaster".to_string(), outputs),
-                FlipFlop(name, outputs) => (name.clone(), outputs),
-                Conjunction(name, outputs) => (name.clone(), outputs),
-            };
-            for output in outputs {
-                m.entry(output.to_string())
-                    .or_default()
-                    .push(name.to_string())
-            }
-            m
-        });
-    let conjunctions: HashMap<String, Vec<String>> = HashMap::from_iter(
-        modules
-            .iter()
-            .filter(|(_, module)| matches!(module, Conjunction(_, _)))
-            .map(|(name, _)| {
-                (
-                    name.clone(),
-                    upstream
-                        .get(name)
-                        .unwrap()
-                        .iter()
-                        .map(|s| s.to_string())
-                        .collect(),
-                )
-            }),
-    );
-
-    let mut memory: HashMap<String, bool> =
-        HashMap::from_iter(modules.iter().filter_map(|(_, module)| match module {
-            FlipFlop(name, _) => Some((name.clone(), false)),
-            Conjunction(name, _) => Some((name.clone(), false)),
-            _ => None,
-        }));
-    let mut low_count = 0usize;
-    let mut high_count = 0usize;
-    let mut queue: VecDeque<(&str, Pulse)> = VecDeque::new();
-
+    let nodes = module_map(parse(input));
+    let mut network = PulseNetwork::new(&nodes);
     for _ in 0..1000 {
-        queue.push_back(("broadcaster", Pulse::Low));
-        low_count += 1;
-        while let Some((name, incoming)) = queue.pop_front() {
-            match modules.get(name) {
-                Some(Broadcaster(outputs)) => {
-                    for output in outputs {
-                        queue.push_back((output, Pulse::Low));
-                        low_count += 1;
-                    }
-                }
-                Some(FlipFlop(name, outputs)) => match incoming {
-                    Pulse::Low => {
-                        let pulse = if memory[name] {
-                            Pulse::Low
-                        } else {
-                            Pulse::High
-                        };
-                        memory
-                            .entry(name.clone())
-                            .and_modify(|v| *v = pulse == Pulse::High);
-                        for output in outputs {
-                            queue.push_back((output, pulse));
-                            match pulse {
-                                Pulse::High => high_count += 1,
-                                Pulse::Low => low_count += 1,
-                            }
-                        }
-                    }
-                    Pulse::High => {}
-                },
-                Some(Conjunction(name, outputs)) => {
-                    let pulse = match conjunctions
-                        .get(name)
-                        .unwrap()
-                        .iter()
-                        .map(|c| memory.get(c).unwrap())
-                        .all(|v| *v)
-                    {
-                        true => Pulse::Low,
-                        false => Pulse::High,
-                    };
-                    memory
-                        .entry(name.clone())
-                        .and_modify(|v| *v = pulse == Pulse::High);
-                    for output in outputs {
-                        queue.push_back((output, pulse));
-                        match pulse {
-                            Pulse::High => high_count += 1,
-                            Pulse::Low => low_count += 1,
-                        }
-                    }
-                }
-                None => {}
-            }
-        }
+        network.press();
     }
+    Some(network.low_count() * network.high_count())
+}
 
-    Some(low_count * high_count)
+pub fn part_two(input: &str) -> Result<usize, Diagnosis> {
+    let nodes = module_map(parse(input));
+    let periods = counter_periods(&nodes, &rx_counters(&nodes)?)?;
+    Ok(lcm_all(periods).expect("rx_counters finds at least one counter"))
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+/// Debug output for `cargo solve 20 --debug <request>`: `dot` prints the network in Graphviz
+/// DOT format, e.g. for `dot -Tsvg`, and `press=N` every pulse of the `N`th button press
+/// followed by the state of every module after it. `press` is short for `press=1`.
+fn debug(input: &str, request: &str) -> Result<String, String> {
     let nodes = module_map(parse(input));
+    let press = match (request, request.strip_prefix("press=")) {
+        ("dot", _) => return Ok(network_dot(&nodes)),
+        ("press", _) => 1,
+        (_, Some(n)) => match n.parse() {
+            Ok(n) if n > 0 => n,
+            _ => return Err(format!("invalid press {n:?}")),
+        },
+        _ => {
+            return Err(format!(
+                "unknown request {request:?}, expected dot or press=N"
+            ))
+        }
+    };
+    let mut network = PulseNetwork::new(&nodes);
+    let trace = (0..press).map(|_| network.press()).last().unwrap();
+    let pulses = trace.iter().map(Signal::to_string).join("\n");
+    let states = network
+        .snapshot()
+        .iter()
+        .map(|(name, state)| format!("{name}: {state:?}"))
+        .join("\n");
+    Ok(format!("{pulses}\n\n{states}"))
+}
 
-    let mut conjunctions: HashMap<String, Option<usize>> = HashMap::from_iter(
-        upstream_conjunctions("cs", &nodes)
-            .into_iter()
-            .skip(1)
-            .map(|name| (name, None)),
-    );
+/// How many button presses to simulate before giving up on a counter.
+const MAX_PRESSES: usize = 100_000;
 
-    let mut memory: HashMap<String, bool> =
-        HashMap::from_iter(nodes.keys().map(|name| (name.to_string(), false)));
-
-    let mut cycles: BTreeSet<usize> = BTreeSet::new();
-    let mut iteration = 0usize;
-    while !conjunctions.is_empty() {
-        let mut queue: VecDeque<(&str, Pulse)> = VecDeque::new();
-        queue.push_back(("broadcaster", Pulse::Low));
-        while let Some((name, incoming)) = queue.pop_front() {
-            match nodes.get(name) {
-                None => {}
-                Some(m) => match m.module_type {
-                    ModuleType::Broadcaster => {
-                        for output in m.downstream.iter() {
-                            queue.push_back((output, Pulse::Low));
-                        }
-                    }
-                    ModuleType::Conjunction => {
-                        let pulse = match m
-                            .upstream
-                            .iter()
-                            .map(|c| memory.get(c).unwrap())
-                            .all(|v| *v)
-                        {
-                            true => Pulse::Low,
-                            false => Pulse::High,
-                        };
-                        if pulse == Pulse::Low {
-                            match conjunctions.get(name) {
-                                Some(None) => {
-                                    conjunctions
-                                        .entry(name.to_string())
-                                        .and_modify(|value| *value = Some(iteration));
-                                }
-                                Some(Some(first)) => {
-                                    cycles.insert(iteration.sub(*first));
-                                    conjunctions.remove(name);
-                                }
-                                _ => {}
-                            }
-                        }
-                        memory
-                            .entry(name.to_string())
-                            .and_modify(|v| *v = pulse == Pulse::High);
-                        for output in m.downstream.iter() {
-                            queue.push_back((output, pulse));
-                        }
-                    }
-                    ModuleType::FlipFlop => match incoming {
-                        Pulse::Low => {
-                            let pulse = if memory[name] {
-                                Pulse::Low
-                            } else {
-                                Pulse::High
-                            };
-                            memory
-                                .entry(name.to_string())
-                                .and_modify(|v| *v = pulse == Pulse::High);
-                            for output in m.downstream.iter() {
-                                queue.push_back((output, pulse));
-                            }
-                        }
-                        Pulse::High => {}
-                    },
-                },
+/// Why the network does not have the shape part two relies on: `rx` fed by a single
+/// conjunction, whose inputs are independent counters that each fire on a fixed period.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Diagnosis {
+    NoRx,
+    SeveralFeeders(Vec<String>),
+    FeederNotConjunction(String),
+    NoCounters(String),
+    SharedModule {
+        counters: (String, String),
+        module: String,
+    },
+    NeverFired(String),
+    NotPeriodic {
+        counter: String,
+        first: usize,
+        second: usize,
+    },
+}
+
+impl Display for Diagnosis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Diagnosis::NoRx => write!(f, "no module sends pulses to rx"),
+            Diagnosis::SeveralFeeders(feeders) => write!(
+                f,
+                "rx is fed by {} modules ({}) rather than a single conjunction",
+                feeders.len(),
+                feeders.join(", ")
+            ),
+            Diagnosis::FeederNotConjunction(feeder) => {
+                write!(f, "rx is fed by {feeder}, which is not a conjunction")
+            }
+            Diagnosis::NoCounters(feeder) => {
+                write!(f, "rx is fed by {feeder}, which has no inputs")
             }
+            Diagnosis::SharedModule { counters, module } => write!(
+                f,
+                "counters {} and {} both depend on {module}, so they are not independent",
+                counters.0, counters.1
+            ),
+            Diagnosis::NeverFired(counter) => write!(
+                f,
+                "counter {counter} did not send two high pulses within {MAX_PRESSES} presses"
+            ),
+            Diagnosis::NotPeriodic {
+                counter,
+                first,
+                second,
+            } => write!(
+                f,
+                "counter {counter} fired at presses {first} and {second}, not on a fixed period from the start"
+            ),
         }
-        iteration += 1;
     }
-    Some(cycles.into_iter().reduce(lcm).unwrap_or(0))
 }
 
-/// https://www.hackertouch.com/least-common-multiple-in-rust.html
-/// Lets not waste time reimplementing basics.
-fn lcm(first: usize, second: usize) -> usize {
-    first * second / gcd(first, second)
-}
-
-fn gcd(first: usize, second: usize) -> usize {
-    let mut max = first;
-    let mut min = second;
-    if min > max {
-        std::mem::swap(&mut max, &mut min);
+/// The inputs of the conjunction feeding `rx`, each checked to depend on modules of its own.
+fn rx_counters(modules: &HashMap<String, ModuleNode>) -> Result<Vec<String>, Diagnosis> {
+    let feeders: Vec<&String> = modules
+        .iter()
+        .filter(|(_, node)| node.downstream.contains("rx"))
+        .map(|(name, _)| name)
+        .sorted()
+        .collect();
+    let feeder = match feeders[..] {
+        [] => return Err(Diagnosis::NoRx),
+        [feeder] => feeder,
+        _ => {
+            return Err(Diagnosis::SeveralFeeders(
+                feeders.into_iter().cloned().collect(),
+            ))
+        }
+    };
+    if modules[feeder].module_type != ModuleType::Conjunction {
+        return Err(Diagnosis::FeederNotConjunction(feeder.clone()));
     }
 
-    loop {
-        let res = max % min;
-        if res == 0 {
-            return min;
+    let counters = modules[feeder].upstream.iter().cloned().collect_vec();
+    if counters.is_empty() {
+        return Err(Diagnosis::NoCounters(feeder.clone()));
+    }
+    let dependencies = counters
+        .iter()
+        .map(|counter| upstream_modules(counter, modules))
+        .collect_vec();
+    for ((a, a_modules), (b, b_modules)) in counters.iter().zip(&dependencies).tuple_combinations()
+    {
+        if let Some(module) = a_modules.intersection(b_modules).next() {
+            return Err(Diagnosis::SharedModule {
+                counters: (a.clone(), b.clone()),
+                module: module.clone(),
+            });
         }
+    }
+    Ok(counters)
+}
+
+/// The press on which each counter first sends a high pulse, checked to repeat on that period.
+fn counter_periods(
+    nodes: &HashMap<String, ModuleNode>,
+    counters: &[String],
+) -> Result<Vec<usize>, Diagnosis> {
+    let mut fired: HashMap<&str, Vec<usize>> =
+        HashMap::from_iter(counters.iter().map(|name| (name.as_str(), vec![])));
 
-        max = min;
-        min = res;
+    let mut network = PulseNetwork::new(nodes);
+    while network.presses() < MAX_PRESSES && fired.values().any(|presses| presses.len() < 2) {
+        let press = network.presses() + 1;
+        for signal in network.press() {
+            if signal.pulse == Pulse::High {
+                if let Some(presses) = fired.get_mut(signal.from) {
+                    if presses.last() != Some(&press) {
+                        presses.push(press);
+                    }
+                }
+            }
+        }
     }
+
+    counters
+        .iter()
+        .map(|counter| match fired[counter.as_str()][..] {
+            [first, second, ..] if second == 2 * first => Ok(first),
+            [first, second, ..] => Err(Diagnosis::NotPeriodic {
+                counter: counter.clone(),
+                first,
+                second,
+            }),
+            _ => Err(Diagnosis::NeverFired(counter.clone())),
+        })
+        .collect()
 }
-fn upstream_conjunctions(start: &str, modules: &HashMap<String, ModuleNode>) -> Vec<String> {
+
+/// Every module that can send pulses, directly or indirectly, to `start`; apart from the
+/// broadcaster, which everything depends on.
+fn upstream_modules(start: &str, modules: &HashMap<String, ModuleNode>) -> BTreeSet<String> {
     let mut dependencies: BTreeSet<String> = BTreeSet::new();
-    let mut upstream: Vec<String> = vec![];
     let mut queue: VecDeque<String> = VecDeque::from([start.to_string()]);
     while let Some(current) = queue.pop_front() {
         if let Some(node) = modules.get(&current) {
-            if node.module_type == ModuleType::Conjunction {
-                upstream.push(current.clone());
-            }
             for name in node.upstream.iter() {
-                if dependencies.insert(name.to_string()) {
+                if name != "broadcaster" && dependencies.insert(name.to_string()) {
                     queue.push_back(name.to_string());
                 }
             }
         }
     }
-    upstream
+    dependencies
+}
+
+/// The module network in Graphviz DOT format, with a shape per module type and, when
+/// part two's structure holds, each counter feeding `rx` drawn as its own cluster.
+fn network_dot(nodes: &HashMap<String, ModuleNode>) -> String {
+    let graph = DirectedGraph::from_edges(
+        nodes
+            .iter()
+            .sorted_by_key(|(name, _)| *name)
+            .flat_map(|(name, node)| node.downstream.iter().map(|to| (name.clone(), to.clone()))),
+    );
+    let clusters = rx_counters(nodes)
+        .map(|counters| {
+            counters
+                .into_iter()
+                .map(|counter| {
+                    let mut members = upstream_modules(&counter, nodes);
+                    members.insert(counter.clone());
+                    (
+                        format!("counter {counter}"),
+                        members.into_iter().collect_vec(),
+                    )
+                })
+                .collect_vec()
+        })
+        .unwrap_or_default();
+
+    graph.to_dot_with(
+        |name| {
+            match nodes.get(name).map(|node| &node.module_type) {
+                Some(ModuleType::Broadcaster) => "shape=doubleoctagon",
+                Some(ModuleType::FlipFlop) => "shape=box",
+                Some(ModuleType::Conjunction) => {
+                    "shape=invhouse, style=filled, fillcolor=lightblue"
+                }
+                None => "shape=plaintext",
+            }
+            .to_string()
+        },
+        &clusters,
+    )
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -292,6 +301,144 @@ enum Pulse {
     Low,
 }
 
+impl Display for Pulse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pulse::High => write!(f, "high"),
+            Pulse::Low => write!(f, "low"),
+        }
+    }
+}
+
+/// What a module remembers between pulses.
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum ModuleState<'a> {
+    Broadcaster,
+    /// Whether the flip-flop is on.
+    FlipFlop(bool),
+    /// The last pulse received from each input.
+    Conjunction(BTreeMap<&'a str, Pulse>),
+}
+
+/// A pulse on its way from one module to another.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct Signal<'a> {
+    from: &'a str,
+    to: &'a str,
+    pulse: Pulse,
+}
+
+impl Display for Signal<'_> {
+    /// The puzzle's own notation, like `a -high-> b`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -{}-> {}", self.from, self.pulse, self.to)
+    }
+}
+
+/// The module network together with the state of every module, one button press at a time.
+struct PulseNetwork<'a> {
+    nodes: &'a HashMap<String, ModuleNode>,
+    states: HashMap<&'a str, ModuleState<'a>>,
+    presses: usize,
+    low_count: usize,
+    high_count: usize,
+}
+
+impl<'a> PulseNetwork<'a> {
+    /// The network with every flip-flop off and every conjunction remembering low pulses.
+    fn new(nodes: &'a HashMap<String, ModuleNode>) -> Self {
+        let states = HashMap::from_iter(nodes.iter().map(|(name, node)| {
+            let state = match node.module_type {
+                ModuleType::Broadcaster => ModuleState::Broadcaster,
+                ModuleType::FlipFlop => ModuleState::FlipFlop(false),
+                ModuleType::Conjunction => ModuleState::Conjunction(BTreeMap::from_iter(
+                    node.upstream
+                        .iter()
+                        .map(|input| (input.as_str(), Pulse::Low)),
+                )),
+            };
+            (name.as_str(), state)
+        }));
+        PulseNetwork {
+            nodes,
+            states,
+            presses: 0,
+            low_count: 0,
+            high_count: 0,
+        }
+    }
+
+    /// Pushes the button once, returning every pulse sent in the order they were handled.
+    fn press(&mut self) -> Vec<Signal<'a>> {
+        let mut trace = vec![];
+        self.presses += 1;
+        let mut queue = VecDeque::from([Signal {
+            from: "button",
+            to: "broadcaster",
+            pulse: Pulse::Low,
+        }]);
+        while let Some(signal) = queue.pop_front() {
+            match signal.pulse {
+                Pulse::High => self.high_count += 1,
+                Pulse::Low => self.low_count += 1,
+            }
+            trace.push(signal);
+
+            let Some((name, node)) = self.nodes.get_key_value(signal.to) else {
+                continue;
+            };
+            let output = match self.states.get_mut(name.as_str()) {
+                Some(ModuleState::Broadcaster) => signal.pulse,
+                Some(ModuleState::FlipFlop(on)) => match signal.pulse {
+                    Pulse::High => continue,
+                    Pulse::Low => {
+                        *on = !*on;
+                        match on {
+                            true => Pulse::High,
+                            false => Pulse::Low,
+                        }
+                    }
+                },
+                Some(ModuleState::Conjunction(memory)) => {
+                    memory.insert(signal.from, signal.pulse);
+                    match memory.values().all(|pulse| *pulse == Pulse::High) {
+                        true => Pulse::Low,
+                        false => Pulse::High,
+                    }
+                }
+                None => continue,
+            };
+            queue.extend(node.downstream.iter().map(|to| Signal {
+                from: name,
+                to,
+                pulse: output,
+            }));
+        }
+        trace
+    }
+
+    fn presses(&self) -> usize {
+        self.presses
+    }
+
+    fn low_count(&self) -> usize {
+        self.low_count
+    }
+
+    fn high_count(&self) -> usize {
+        self.high_count
+    }
+
+    /// A copy of every module's state, sorted by name.
+    fn snapshot(&self) -> BTreeMap<&'a str, ModuleState<'a>> {
+        BTreeMap::from_iter(
+            self.states
+                .iter()
+                .map(|(name, state)| (*name, state.clone())),
+        )
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 enum Module {
     Broadcaster(Vec<String>),
@@ -387,4 +534,103 @@ mod tests {
         let result = part_one(&advent_of_code::template::read_example("20-2"));
         assert_eq!(result, Some(11687500));
     }
+
+    #[test]
+    fn traces_a_press() {
+        let nodes = module_map(parse(&advent_of_code::template::read_file("examples", DAY)));
+        let mut network = PulseNetwork::new(&nodes);
+        let trace = network.press().iter().map(Signal::to_string).collect_vec();
+        assert_eq!(
+            trace,
+            vec![
+                "button -low-> broadcaster",
+                "broadcaster -low-> a",
+                "broadcaster -low-> b",
+                "broadcaster -low-> c",
+                "a -high-> b",
+                "b -high-> c",
+                "c -high-> inv",
+                "inv -low-> a",
+                "a -low-> b",
+                "b -low-> c",
+                "c -low-> inv",
+                "inv -high-> a",
+            ]
+        );
+        assert_eq!((network.low_count(), network.high_count()), (8, 4));
+    }
+
+    #[test]
+    fn snapshots_module_state() {
+        let nodes = module_map(parse(&advent_of_code::template::read_example("20-2")));
+        let mut network = PulseNetwork::new(&nodes);
+        let initial = network.snapshot();
+
+        network.press();
+        let states = network.snapshot();
+        assert_eq!(states["a"], ModuleState::FlipFlop(true));
+        assert_eq!(states["b"], ModuleState::FlipFlop(true));
+        assert_eq!(
+            states["con"],
+            ModuleState::Conjunction(BTreeMap::from([("a", Pulse::High), ("b", Pulse::High)]))
+        );
+
+        (0..3).for_each(|_| {
+            network.press();
+        });
+        assert_eq!(network.snapshot(), initial);
+        assert_eq!(network.presses(), 4);
+    }
+
+    #[test]
+    fn debugs_a_press() {
+        let input = advent_of_code::template::read_example("20-2");
+        let output = debug(&input, "press=2").unwrap();
+        let (pulses, states) = output.split_once("\n\n").unwrap();
+        assert_eq!(pulses.lines().next(), Some("button -low-> broadcaster"));
+        assert_eq!(pulses.lines().count(), 6);
+        assert!(states.contains("a: FlipFlop(false)\nb: FlipFlop(true)\n"));
+        assert_eq!(debug(&input, "press"), debug(&input, "press=1"));
+        assert!(debug(&input, "press=0").is_err());
+    }
+
+    #[test]
+    fn exports_the_network() {
+        let nodes = module_map(parse(&advent_of_code::template::read_example(
+            "20-counters",
+        )));
+        let dot = network_dot(&nodes);
+        assert!(
+            dot.starts_with("digraph {\n    subgraph cluster_0 {\n        label=\"counter ia\";\n")
+        );
+        assert!(
+            dot.contains("        \"ca\" [shape=invhouse, style=filled, fillcolor=lightblue];\n")
+        );
+        assert!(dot.contains("    \"broadcaster\" [shape=doubleoctagon];\n"));
+        assert!(dot.contains("    \"rx\" [shape=plaintext];\n"));
+        assert!(dot.contains("    \"hub\" -> \"rx\";\n"));
+        assert_eq!(dot.matches("[shape=box]").count(), 8);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_example("20-counters"));
+        assert_eq!(result, Ok(143));
+    }
+
+    #[test]
+    fn diagnoses_unsupported_networks() {
+        let nodes = module_map(parse(&advent_of_code::template::read_file("examples", DAY)));
+        assert_eq!(rx_counters(&nodes), Err(Diagnosis::NoRx));
+
+        let input = advent_of_code::template::read_example("20-counters");
+        let nodes = module_map(parse(&input.replace("%bb -> bc", "%bb -> bc, ad")));
+        assert_eq!(
+            rx_counters(&nodes),
+            Err(Diagnosis::SharedModule {
+                counters: ("ia".to_string(), "ib".to_string()),
+                module: "ba".to_string(),
+            })
+        );
+    }
 }
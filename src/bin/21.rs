@@ -1,124 +1,244 @@
-use itertools::Itertools;
-use num::Integer;
-use std::collections::{HashSet, VecDeque};
-use std::ops::Mul;
+use advent_of_code::bits::BitGrid;
+use advent_of_code::math::lcm;
+use std::fmt::Display;
 
 advent_of_code::solution!(21);
 
 pub fn part_one(input: &str) -> Option<usize> {
-    Some(possibilities(
-        64,
-        input
-            .lines()
-            .map(|line| line.chars().collect_vec())
-            .collect_vec(),
-    ))
+    Some(Garden::from(input).reachable(64))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    Some(diamonds(
-        26501365,
-        input
-            .lines()
-            .map(|line| line.chars().collect_vec())
-            .collect_vec(),
-    ))
+    Garden::from(input).reachable_infinite(26501365)
 }
 
-fn possibilities(steps: usize, garden: Vec<Vec<char>>) -> usize {
-    let width = garden.len();
-    assert_eq!(garden.len(), garden[0].len()); //asserting square
-    assert_eq!(width % 2, 1); // asserting odd width
-    let midpoint = width / 2;
-    assert_eq!(garden[midpoint][midpoint], 'S');
-
-    let start = (midpoint, midpoint);
-    let mut even = 0usize;
-    let mut odd = 0usize;
-    let mut queue = VecDeque::from([(0usize, midpoint, midpoint)]);
-    let mut visisted = HashSet::from([start]);
-    while let Some((step, row, col)) = queue.pop_front() {
-        if step <= steps {
-            if step.is_even() {
-                even += 1;
-            } else {
-                odd += 1;
+type Position = (usize, usize);
+
+/// How many times the second difference of the samples has to repeat before trusting it
+/// in a garden without the properties of [`Garden::check`].
+const REPEATS: usize = 3;
+/// How many repeats of the garden to sample before giving up on extrapolating.
+const MAX_SAMPLES: usize = 32;
+/// The most cells the walk may cover, copies included, before giving up. For gardens that are
+/// far from square, the samples are so far apart that the copies would not fit in memory.
+const MAX_CELLS: usize = 1 << 26;
+
+struct Garden {
+    plots: BitGrid,
+    start: Position,
+}
+
+impl From<&str> for Garden {
+    fn from(input: &str) -> Self {
+        let plots = BitGrid::parse(input, |ch| ch != '#');
+        let start = BitGrid::parse(input, |ch| ch == 'S')
+            .iter()
+            .next()
+            .expect("a starting position");
+        Garden { plots, start }
+    }
+}
+
+/// A property of the garden that the real input has, which makes the plots reached after
+/// half a garden plus a whole number of gardens grow quadratically from the very first sample.
+#[derive(Debug, PartialEq, Eq)]
+enum Assumption {
+    Square { width: usize, height: usize },
+    CentredStart(Position),
+    ClearMiddle,
+    HalfWidthSteps { steps: usize, width: usize },
+}
+
+impl Display for Assumption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Assumption::Square { width, height } => {
+                write!(f, "the garden is {width}x{height} rather than square")
             }
-            [
-                (row + 1, col),
-                (row, col + 1),
-                (row.wrapping_sub(1), col),
-                (row, col.wrapping_sub(1)),
-            ]
-            .into_iter()
-            .filter(|(r, c)| *r < width && *c < width)
-            .for_each(|(r, c)| {
-                if visisted.insert((r, c)) && garden[r][c] == '.' {
-                    queue.push_back((step + 1, r, c));
-                }
-            })
+            Assumption::CentredStart((row, col)) => {
+                write!(f, "the start ({row}, {col}) is not in the centre")
+            }
+            Assumption::ClearMiddle => write!(f, "the middle row or column has rocks"),
+            Assumption::HalfWidthSteps { steps, width } => write!(
+                f,
+                "{steps} steps is not half a garden more than a multiple of {width}"
+            ),
         }
     }
-    if steps.is_even() {
-        even
-    } else {
-        odd
-    }
 }
 
-fn diamonds(steps: usize, garden: Vec<Vec<char>>) -> usize {
-    // let width = garden.len();
-    let cycles = steps / garden.len();
-    assert_eq!(cycles % 2, 0);
+impl Garden {
+    fn width(&self) -> usize {
+        self.plots.width()
+    }
+
+    fn height(&self) -> usize {
+        self.plots.height()
+    }
+
+    /// The plots that can be reached in exactly `steps` steps, without leaving the garden.
+    fn reachable(&self, steps: usize) -> usize {
+        let mut reached = BitGrid::new(self.width(), self.height());
+        reached.insert(self.start.0, self.start.1);
+        for _ in 0..steps {
+            reached = reached.neighbours() & &self.plots;
+        }
+        reached.len()
+    }
 
-    let (even, odd, corner) = diamond_count(garden);
-    let even_diamonds = (1 + cycles.div_ceil(2).mul(2)).pow(2);
-    let odd_diamonds = (cycles.div_ceil(2).mul(2)).pow(2);
-    let corner_diamonds = (cycles * 2 + 1).pow(2) / 4;
+    /// The first property of the real input that this garden lacks, if any.
+    fn check(&self, steps: usize) -> Result<(), Assumption> {
+        let (width, height) = (self.width(), self.height());
+        let middle = width / 2;
+        if width != height {
+            return Err(Assumption::Square { width, height });
+        }
+        if width % 2 == 0 || self.start != (middle, middle) {
+            return Err(Assumption::CentredStart(self.start));
+        }
+        if (0..width).any(|i| !self.plots.contains(middle, i) || !self.plots.contains(i, middle)) {
+            return Err(Assumption::ClearMiddle);
+        }
+        if steps % width != middle {
+            return Err(Assumption::HalfWidthSteps { steps, width });
+        }
+        Ok(())
+    }
 
-    corner_diamonds * corner
-        + match steps % 2 == 0 {
-            true => even_diamonds * even + odd_diamonds * odd,
-            false => even_diamonds * odd + odd_diamonds * even,
+    /// The plots that can be reached in exactly `steps` steps in the garden repeated infinitely
+    /// in every direction.
+    ///
+    /// Once the walk has spread past the first few gardens, the plots reached after `offset +
+    /// k * period` steps grow quadratically in `k`, so the count is extrapolated from samples
+    /// as soon as their second difference repeats. With the real input's properties that is
+    /// true from the first sample; otherwise it has to repeat [`REPEATS`] times.
+    fn reachable_infinite(&self, steps: usize) -> Option<usize> {
+        let (period, repeats) = match self.check(steps) {
+            Ok(()) => (self.width(), 1),
+            Err(_) => (lcm(self.width(), self.height()), REPEATS),
+        };
+        let offset = steps % period;
+        let mut walk = Walk::new(self);
+        // Extrapolating takes at least three samples and a repeat.
+        if !walk.fits(steps.min(offset + (repeats + 2) * period)) {
+            return None;
         }
+        let mut samples: Vec<i64> = vec![];
+        let mut repeated = 0;
+        for k in 0..MAX_SAMPLES {
+            let at = offset + k * period;
+            if at >= steps {
+                return walk.reachable(steps);
+            }
+            samples.push(walk.reachable(at)? as i64);
+            if let [.., a, b, c, d] = samples[..] {
+                repeated = match c - 2 * b + a == d - 2 * c + b {
+                    true => repeated + 1,
+                    false => 0,
+                };
+            }
+            if repeated == repeats {
+                let [.., b, c, d] = samples[..] else {
+                    unreachable!()
+                };
+                let t = ((steps - at) / period) as i64;
+                let (first, second) = (d - c, d - 2 * c + b);
+                return usize::try_from(d + t * first + t * (t + 1) / 2 * second).ok();
+            }
+        }
+        None
+    }
 }
 
-fn diamond_count(garden: Vec<Vec<char>>) -> (usize, usize, usize) {
-    let width = garden.len();
-    assert_eq!(garden.len(), garden[0].len()); //asserting square
-    assert_eq!(width % 2, 1); // asserting odd width
-    let midpoint = width / 2;
-    assert_eq!(garden[midpoint][midpoint], 'S');
-
-    let start = (midpoint, midpoint);
-    let mut even = 0usize;
-    let mut odd = 0usize;
-    let mut corner = 0usize;
-    let mut queue = VecDeque::from([(0usize, midpoint, midpoint)]);
-    let mut visisted = HashSet::from([start]);
-    while let Some((step, row, col)) = queue.pop_front() {
-        if step > midpoint {
-            corner += 1;
-        } else if step.is_even() {
-            even += 1;
-        } else {
-            odd += 1;
+/// The plots reached in the garden repeated infinitely in every direction, stepped on just
+/// enough copies of the garden to hold them, adding more copies as the walk spreads.
+struct Walk<'a> {
+    garden: &'a Garden,
+    /// How many copies of the garden there are on each side of the one the walk started in.
+    copies: usize,
+    plots: BitGrid,
+    reached: BitGrid,
+    steps: usize,
+}
+
+impl<'a> Walk<'a> {
+    fn new(garden: &'a Garden) -> Self {
+        let mut reached = BitGrid::new(garden.width(), garden.height());
+        reached.insert(garden.start.0, garden.start.1);
+        Walk {
+            garden,
+            copies: 0,
+            plots: garden.plots.clone(),
+            reached,
+            steps: 0,
+        }
+    }
+
+    /// The plots reached in exactly `steps` steps, which is never fewer than last time,
+    /// unless holding them takes more than [`MAX_CELLS`] cells.
+    fn reachable(&mut self, steps: usize) -> Option<usize> {
+        assert!(
+            steps >= self.steps,
+            "the walk is already {} steps in",
+            self.steps
+        );
+        if !self.fits(steps) {
+            return None;
+        }
+        let copies = (self.copies..)
+            .find(|&copies| self.reach(copies) >= steps)
+            .unwrap();
+        if copies > self.copies {
+            self.grow(copies);
+        }
+        while self.steps < steps {
+            self.reached = self.reached.neighbours() & &self.plots;
+            self.steps += 1;
         }
+        Some(self.reached.len())
+    }
+
+    /// Whether the copies needed to walk `steps` steps have at most [`MAX_CELLS`] cells.
+    fn fits(&self, steps: usize) -> bool {
+        let (width, height) = (self.garden.width(), self.garden.height());
+        let copies = steps.div_ceil(width.min(height));
+        (2 * copies + 1)
+            .checked_pow(2)
+            .and_then(|gardens| gardens.checked_mul(width * height))
+            .is_some_and(|cells| cells <= MAX_CELLS)
+    }
+
+    /// How many steps from the start it is to the edge of `copies` copies on each side.
+    fn reach(&self, copies: usize) -> usize {
+        let (row, col) = self.garden.start;
+        let (width, height) = (self.garden.width(), self.garden.height());
         [
-            (row + 1, col),
-            (row, col + 1),
-            (row.wrapping_sub(1), col),
-            (row, col.wrapping_sub(1)),
+            copies * height + row,
+            copies * height + height - 1 - row,
+            copies * width + col,
+            copies * width + width - 1 - col,
         ]
         .into_iter()
-        .filter(|(r, c)| *r < width && *c < width)
-        .for_each(|(r, c)| {
-            if visisted.insert((r, c)) && garden[r][c] == '.' {
-                queue.push_back((step + 1, r, c));
+        .min()
+        .unwrap()
+    }
+
+    /// Surrounds the garden with `copies` copies on each side, keeping the plots reached.
+    fn grow(&mut self, copies: usize) {
+        let (width, height) = (self.garden.width(), self.garden.height());
+        let mut plots = BitGrid::new(width * (2 * copies + 1), height * (2 * copies + 1));
+        for (row, col) in self.garden.plots.iter() {
+            for (i, j) in (0..=2 * copies).flat_map(|i| (0..=2 * copies).map(move |j| (i, j))) {
+                plots.insert(i * height + row, j * width + col);
             }
-        })
+        }
+        let shift = copies - self.copies;
+        let mut reached = BitGrid::new(plots.width(), plots.height());
+        for (row, col) in self.reached.iter() {
+            reached.insert(row + shift * height, col + shift * width);
+        }
+        (self.copies, self.plots, self.reached) = (copies, plots, reached);
     }
-    (even, odd, corner)
 }
 
 #[cfg(test)]
@@ -127,12 +247,70 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let garden = input
-            .lines()
-            .map(|line| line.chars().collect_vec())
-            .collect_vec();
-        let result = possibilities(6, garden);
-        assert_eq!(result, 16);
+        let garden = Garden::from(advent_of_code::template::read_file("examples", DAY).as_str());
+        assert_eq!(garden.reachable(6), 16);
+    }
+
+    #[test]
+    fn counts_plots_in_an_infinite_garden() {
+        let garden = Garden::from(advent_of_code::template::read_file("examples", DAY).as_str());
+        assert_eq!(garden.check(5000), Err(Assumption::ClearMiddle));
+        for (steps, plots) in [
+            (6, 16),
+            (10, 50),
+            (50, 1594),
+            (100, 6536),
+            (500, 167004),
+            (1000, 668697),
+            (5000, 16733044),
+        ] {
+            assert_eq!(
+                garden.reachable_infinite(steps),
+                Some(plots),
+                "{steps} steps"
+            );
+        }
+    }
+
+    #[test]
+    fn extrapolates_gardens_with_clear_middles() {
+        let garden = Garden::from(
+            "...........\n....#..#.#.\n.###..#.##.\n..#.....#..\n....#.#....\n.....S.....\n\
+             .##.#....#.\n.......##..\n.##.#.#.##.\n.##.#...##.\n...........",
+        );
+        let steps = 5 + 11 * 12;
+        assert_eq!(garden.check(steps), Ok(()));
+        assert_eq!(
+            garden.reachable_infinite(steps),
+            Walk::new(&garden).reachable(steps)
+        );
+        assert_eq!(
+            garden.check(steps + 1),
+            Err(Assumption::HalfWidthSteps {
+                steps: steps + 1,
+                width: 11
+            })
+        );
+    }
+
+    #[test]
+    fn extrapolates_rectangular_gardens() {
+        let garden = Garden::from(".....\n.#.#.\n..S..\n.#...\n...#.\n.....\n..#..");
+        assert_eq!(
+            garden.check(100),
+            Err(Assumption::Square {
+                width: 5,
+                height: 7
+            })
+        );
+        let steps = 3 + 35 * 8;
+        assert_eq!(
+            garden.reachable_infinite(steps),
+            Walk::new(&garden).reachable(steps)
+        );
+
+        let wide =
+            Garden::from(&format!("{}S{}\n", ".".repeat(65), ".".repeat(65)).repeat(130)[..]);
+        assert_eq!(wide.reachable_infinite(26501365), None);
     }
 }
@@ -1,8 +1,8 @@
+use advent_of_code::space::{Aabb, Vec3};
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::hash::Hash;
-use std::ops::RangeInclusive;
 
 advent_of_code::solution!(22);
 
@@ -18,12 +18,13 @@ impl BrickStack {
         let len = bricks.len();
         let mut under: Vec<Vec<usize>> = vec![vec![]; len];
         let mut over: Vec<Vec<usize>> = vec![vec![]; len];
-        for brick in bricks.iter().sorted_by(|a, b| a.z.start().cmp(b.z.start())) {
+        for brick in bricks.iter().sorted_by_key(|b| b.cube.min.z) {
             let bounding_box = Brick {
                 id: brick.id,
-                x: brick.x.clone(),
-                y: brick.y.clone(),
-                z: 0..=(brick.z.start() - 1),
+                cube: Aabb::new(
+                    Vec3::new(brick.cube.min.x, brick.cube.min.y, 0),
+                    Vec3::new(brick.cube.max.x, brick.cube.max.y, brick.cube.min.z - 1),
+                ),
             };
             let mut new_z = 0u32;
             under[brick.id] = stack
@@ -32,10 +33,10 @@ impl BrickStack {
                 .filter(|b| b.intersects(&bounding_box))
                 .take_while(|b| match new_z {
                     0 => {
-                        new_z = *b.z.end();
+                        new_z = b.cube.max.z;
                         true
                     }
-                    l if l == *b.z.end() => true,
+                    l if l == b.cube.max.z => true,
                     _ => false,
                 })
                 .map(|b| b.id)
@@ -98,39 +99,33 @@ pub fn part_two(input: &str) -> Option<usize> {
     )
 }
 
-fn intersects(a: &RangeInclusive<u32>, b: &RangeInclusive<u32>) -> bool {
-    a.start() <= b.end() && a.end() >= b.start()
-}
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 struct Brick {
     id: usize,
-    x: RangeInclusive<u32>,
-    y: RangeInclusive<u32>,
-    z: RangeInclusive<u32>,
+    cube: Aabb<u32>,
 }
 impl Brick {
     fn from_line(line: &str, id: usize) -> Self {
         let (left, right) = line.split_once('~').unwrap();
-        let (x, y, z) = left
-            .splitn(3, ',')
-            .map(|n| n.parse::<u32>().unwrap())
-            .zip(right.splitn(3, ',').map(|n| n.parse::<u32>().unwrap()))
-            .map(|(a, b)| a.min(b)..=a.max(b))
-            .collect_tuple()
-            .unwrap();
-        Brick { id, x, y, z }
+        let corner = |s: &str| -> Vec3<u32> {
+            s.splitn(3, ',')
+                .map(|n| n.parse::<u32>().unwrap())
+                .collect_tuple::<(u32, u32, u32)>()
+                .unwrap()
+                .into()
+        };
+        Brick {
+            id,
+            cube: Aabb::new(corner(left), corner(right)),
+        }
     }
     fn intersects(&self, other: &Brick) -> bool {
-        intersects(&self.x, &other.x)
-            && intersects(&self.y, &other.y)
-            && intersects(&self.z, &other.z)
+        self.cube.overlaps(&other.cube)
     }
     fn drop_z(&self, z: u32) -> Self {
         Brick {
             id: self.id,
-            x: self.x.clone(),
-            y: self.y.clone(),
-            z: RangeInclusive::new(z, z + self.z.end() - self.z.start()),
+            cube: self.cube.at_z(z),
         }
     }
 }
@@ -143,9 +138,9 @@ impl PartialOrd<Self> for Brick {
 
 impl Ord for Brick {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.z.end().cmp(other.z.end()) {
-            Ordering::Equal => match self.x.end().cmp(other.x.end()) {
-                Ordering::Equal => self.y.end().cmp(other.y.end()),
+        match self.cube.max.z.cmp(&other.cube.max.z) {
+            Ordering::Equal => match self.cube.max.x.cmp(&other.cube.max.x) {
+                Ordering::Equal => self.cube.max.y.cmp(&other.cube.max.y),
                 Ordering::Less => Ordering::Less,
                 Ordering::Greater => Ordering::Greater,
             },
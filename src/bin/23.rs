@@ -1,213 +1,165 @@
+use advent_of_code::maze::{Cell, Direction, Junctions, Maze, Tile};
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet, VecDeque};
 
-advent_of_code::solution!(23);
+advent_of_code::solution!(23, debug = debug);
 
-type Position = (usize, usize);
-#[derive(Debug, Clone)]
-struct Grid {
-    data: Vec<Vec<char>>,
-    start: (usize, usize),
-    end: (usize, usize),
+pub fn part_one(input: &str) -> Option<usize> {
+    Trails::from_input(input)
+        .longest_hike(true)
+        .map(|hike| hike.length)
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    Trails::from_input(input)
+        .longest_hike(false)
+        .map(|hike| hike.length)
 }
-impl Grid {
+
+/// Debug output for `cargo solve 23 --debug <request>`: the map with the longest hike drawn
+/// on it, on `slippery` slopes as in part one or `dry` ones as in part two.
+fn debug(input: &str, request: &str) -> Result<String, String> {
+    let slippery = match request {
+        "slippery" => true,
+        "dry" => false,
+        _ => {
+            return Err(format!(
+                "unknown request {request:?}, expected slippery or dry"
+            ))
+        }
+    };
+    Ok(Trails::from_input(input).draw_longest_hike(slippery))
+}
+
+struct Trails {
+    maze: Maze,
+    start: Cell,
+    end: Cell,
+}
+
+impl Trails {
+    /// The map, starting from the open cell in the top row and ending at the one in the bottom row.
     fn from_input(input: &str) -> Self {
-        let data = input.lines().map(|l| l.chars().collect_vec()).collect_vec();
-        assert_eq!(
-            data.len(),
-            data[0].len(),
-            "We are expecting the Grid to be square but it was {}x{}",
-            data.len(),
-            data[0].len()
-        );
-        let start = data
-            .iter()
-            .enumerate()
-            .find_map(|(row, line)| {
-                line.iter().enumerate().find_map(|(col, ch)| match ch {
-                    '.' => Some((row, col)),
-                    _ => None,
-                })
-            })
-            .unwrap();
-        let end = data
-            .iter()
-            .enumerate()
-            .rev()
-            .find_map(|(row, line)| {
-                line.iter()
-                    .enumerate()
-                    .rev()
-                    .find_map(|(col, ch)| match ch {
-                        '.' => Some((row, col)),
-                        _ => None,
-                    })
-            })
-            .unwrap();
-        Grid { data, start, end }
+        let maze = Maze::parse(input, |ch| match ch {
+            '#' => Tile::Wall,
+            '^' => Tile::OneWay(Direction::North),
+            '>' => Tile::OneWay(Direction::East),
+            'v' => Tile::OneWay(Direction::South),
+            '<' => Tile::OneWay(Direction::West),
+            _ => Tile::Open,
+        });
+        let start = maze
+            .open_cells()
+            .next()
+            .expect("an open cell in the top row");
+        let end = maze
+            .open_cells()
+            .last()
+            .expect("an open cell in the bottom row");
+        Trails { maze, start, end }
     }
 
-    fn intersections(&self) -> HashSet<Position> {
-        let mut visited: HashSet<(usize, usize)> = HashSet::new();
-        let mut intersections: HashSet<(usize, usize)> = HashSet::new();
-        let mut queue = VecDeque::from([self.start]);
-        while let Some(head) = queue.pop_front() {
-            if visited.insert(head) {
-                let neighbors = self.neighbors(head);
-                if neighbors.len() > 2 {
-                    intersections.insert(head);
-                }
-                for neighbor in neighbors {
-                    if !visited.contains(&neighbor) {
-                        queue.push_back(neighbor);
-                    }
-                }
-            }
-        }
-        intersections
+    fn junctions(&self, slippery: bool) -> Junctions {
+        self.maze.junctions([self.start, self.end], slippery)
     }
 
-    fn neighbors(&self, (row, col): (usize, usize)) -> Vec<(usize, usize)> {
-        [
-            (row.checked_add(1), Some(col)),
-            (row.checked_sub(1), Some(col)),
-            (Some(row), col.checked_add(1)),
-            (Some(row), col.checked_sub(1)),
-        ]
-        .into_iter()
-        .filter_map(|n| match n {
-            (Some(row), Some(col)) => Some((row, col)),
-            _ => None,
-        })
-        .filter(|(row, col)| *row < self.data.len() && *col < self.data.len())
-        .filter(|(row, col)| !matches!(self.data[*row][*col], '#'))
-        .collect_vec()
+    /// The longest hike that never visits a cell twice. On slippery slopes, you can only go downhill.
+    fn longest_hike(&self, slippery: bool) -> Option<Hike> {
+        JunctionGraph::new(&self.junctions(slippery)).longest_path()
     }
 
-    fn directed_neighbors(&self, (row, col): (usize, usize)) -> Vec<(usize, usize)> {
-        match self.data[row][col] {
-            '.' => vec![
-                (row.checked_add(1), Some(col)),
-                (row.checked_sub(1), Some(col)),
-                (Some(row), col.checked_add(1)),
-                (Some(row), col.checked_sub(1)),
-            ],
-            '>' => vec![(Some(row), col.checked_add(1))],
-            '<' => vec![(Some(row), col.checked_sub(1))],
-            '^' => vec![(row.checked_sub(1), Some(col))],
-            'v' => vec![(row.checked_add(1), Some(col))],
-            _ => panic!("Unexpected entry"),
+    /// The map with the longest hike drawn on it.
+    fn draw_longest_hike(&self, slippery: bool) -> String {
+        let junctions = self.junctions(slippery);
+        let route = JunctionGraph::new(&junctions)
+            .longest_path()
+            .map(|hike| junctions.cells(&hike.corridors))
+            .unwrap_or_default();
+        self.maze.draw(&route)
+    }
+}
+
+/// A route between junctions, as `(junction, corridor)` pairs that index into `Junctions::corridors`.
+struct Hike {
+    length: usize,
+    corridors: Vec<(usize, usize)>,
+}
+
+/// Just the corridor lengths of a `Junctions`, whose waypoints make the start junction 0
+/// and the end junction 1. At most 64 junctions, so a set of them fits in a `u64`.
+struct JunctionGraph {
+    /// The corridors leaving each junction, as `(junction, length)`.
+    edges: Vec<Vec<(usize, usize)>>,
+    start: usize,
+    end: usize,
+}
+
+impl JunctionGraph {
+    fn new(junctions: &Junctions) -> Self {
+        assert!(
+            junctions.cells.len() <= 64,
+            "{} junctions do not fit in a u64 visited mask",
+            junctions.cells.len()
+        );
+        let edges = junctions
+            .corridors
+            .iter()
+            .map(|corridors| {
+                corridors
+                    .iter()
+                    .map(|corridor| (corridor.to, corridor.length()))
+                    .collect_vec()
+            })
+            .collect_vec();
+        JunctionGraph {
+            edges,
+            start: 0,
+            end: 1,
         }
-        .into_iter()
-        .filter_map(|n| match n {
-            (Some(row), Some(col)) => Some((row, col)),
+    }
+
+    /// The longest route from start to end that never revisits a junction.
+    fn longest_path(&self) -> Option<Hike> {
+        // When a single junction leads to the end, leaving it any other way can never
+        // come back to the end, so from there the only route worth trying is straight on.
+        let exits = (0..self.edges.len())
+            .filter(|&from| self.edges[from].iter().any(|&(to, _)| to == self.end))
+            .collect_vec();
+        let exit = match exits[..] {
+            [exit] => Some(exit),
             _ => None,
-        })
-        .filter(|(row, col)| *row < self.data.len() && *col < self.data.len())
-        .filter(|(row, col)| !matches!(self.data[*row][*col], '#'))
-        .collect_vec()
+        };
+        let mut best = None;
+        self.search(self.start, 1 << self.start, 0, exit, &mut vec![], &mut best);
+        best
     }
 
-    fn to_graph(
+    fn search(
         &self,
-        directed: bool,
-    ) -> (
-        HashSet<Position>,
-        HashMap<Position, HashMap<Position, usize>>,
+        junction: usize,
+        visited: u64,
+        length: usize,
+        exit: Option<usize>,
+        route: &mut Vec<(usize, usize)>,
+        best: &mut Option<Hike>,
     ) {
-        let nodes: HashSet<Position> = HashSet::from_iter(
-            vec![self.start, self.end]
-                .into_iter()
-                .chain(self.intersections().iter().copied()),
-        );
-
-        let edges: HashMap<Position, HashMap<Position, usize>> =
-            HashMap::from_iter(nodes.clone().into_iter().map(|i| {
-                let mut my_edges: HashMap<Position, usize> = HashMap::new();
-                let mut queue: VecDeque<Vec<Position>> = VecDeque::from_iter([vec![i]]);
-                while let Some(list) = queue.pop_front() {
-                    if let Some(p) = list.last() {
-                        for n in if directed {
-                            self.directed_neighbors(*p)
-                        } else {
-                            self.neighbors(*p)
-                        }
-                        .into_iter()
-                        .filter(|n| !list.contains(n))
-                        {
-                            if nodes.contains(&n) {
-                                my_edges.insert(n, list.len());
-                            } else {
-                                queue.push_back(
-                                    list.clone().into_iter().chain(vec![n]).collect_vec(),
-                                );
-                            }
-                        }
-                    }
-                }
-                (i, my_edges)
-            }));
-        (nodes, edges)
-    }
-}
-pub fn part_one(input: &str) -> Option<usize> {
-    let grid = Grid::from_input(input);
-    let (_, edges) = grid.to_graph(true);
-    let mut paths: Vec<Vec<Position>> = vec![];
-    let mut q = VecDeque::from([vec![grid.start]]);
-    while let Some(list) = q.pop_front() {
-        if let Some(last) = list.last() {
-            if last == &grid.end {
-                paths.push(list);
-            } else {
-                for p in edges.get(last).unwrap().keys() {
-                    q.push_back(list.clone().into_iter().chain(vec![*p]).collect_vec());
-                }
+        if junction == self.end {
+            if best.as_ref().is_none_or(|hike| length > hike.length) {
+                *best = Some(Hike {
+                    length,
+                    corridors: route.clone(),
+                });
             }
+            return;
         }
-    }
-    paths
-        .into_iter()
-        .map(|path| {
-            path.iter()
-                .tuple_windows()
-                .map(|(from, to)| edges.get(from).unwrap().get(to).unwrap())
-                .sum()
-        })
-        .max()
-}
-
-pub fn part_two(input: &str) -> Option<usize> {
-    let grid = Grid::from_input(input);
-    let (_, edges) = grid.to_graph(false);
-    let last_line = edges.get(&grid.end).unwrap();
-    assert!(last_line.len() == 1);
-    let target = last_line.keys().next().unwrap();
-    let mut paths: Vec<Vec<Position>> = vec![];
-    let mut q = VecDeque::from([vec![grid.start]]);
-    while let Some(list) = q.pop_front() {
-        if let Some(last) = list.last() {
-            if last == target {
-                paths.push(list.clone().into_iter().chain(vec![grid.end]).collect_vec());
-            } else {
-                for p in edges.get(last).unwrap().keys() {
-                    if !list.contains(p) {
-                        q.push_back(list.clone().into_iter().chain(vec![*p]).collect_vec());
-                    }
-                }
+        for (corridor, &(to, steps)) in self.edges[junction].iter().enumerate() {
+            if visited & (1 << to) != 0 || (Some(junction) == exit && to != self.end) {
+                continue;
             }
+            route.push((junction, corridor));
+            self.search(to, visited | (1 << to), length + steps, exit, route, best);
+            route.pop();
         }
     }
-    // println!("Path size: {}",paths.len());
-    paths
-        .into_iter()
-        .map(|path| {
-            path.iter()
-                .tuple_windows()
-                .map(|(from, to)| edges.get(from).unwrap().get(to).unwrap())
-                .sum()
-        })
-        .max()
 }
 
 #[cfg(test)]
@@ -225,4 +177,13 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(154));
     }
+
+    #[test]
+    fn draws_the_longest_hike() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let map = debug(&input, "slippery").unwrap();
+        assert_eq!(map.matches('O').count(), 95);
+        assert!(map.starts_with("#O#####"));
+        assert_eq!(debug(&input, "dry").unwrap().matches('O').count(), 155);
+    }
 }
@@ -1,178 +1,133 @@
 use crate::parse::parse;
+use advent_of_code::linear::{solve, BigRational, Rational};
+use advent_of_code::space::{Line, Vec3};
 use itertools::Itertools;
-use std::fmt::{Display, Formatter};
-use std::ops::{RangeInclusive, Sub};
+use num::{BigInt, ToPrimitive};
+use std::ops::RangeInclusive;
 
 advent_of_code::solution!(24);
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
-pub struct Hailstone {
-    x: i128,
-    y: i128,
-    z: i128,
-    dx: i128,
-    dy: i128,
-    dz: i128,
-}
-impl From<(i128, i128, i128, i128, i128, i128)> for Hailstone {
-    fn from((x, y, z, dx, dy, dz): (i128, i128, i128, i128, i128, i128)) -> Self {
-        Hailstone {
-            x,
-            y,
-            z,
-            dx,
-            dy,
-            dz,
-        }
-    }
-}
-impl From<Hailstone> for (i128, i128, i128, i128, i128, i128) {
-    fn from(val: Hailstone) -> Self {
-        (val.x, val.y, val.z, val.dx, val.dy, val.dz)
-    }
-}
-impl Sub for Hailstone {
-    type Output = Hailstone;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Hailstone {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-            dx: self.dx - rhs.dx,
-            dy: self.dy - rhs.dy,
-            dz: self.dz - rhs.dz,
-        }
-    }
-}
-impl Display for Hailstone {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
-    }
-}
-impl Hailstone {
-    fn velocity_delta(&self, other: &Hailstone, time: i128) -> Hailstone {
-        Hailstone {
-            x: 0,
-            y: 0,
-            z: 0,
-            dx: (other.x - self.x) / time,
-            dy: (other.y - self.y) / time,
-            dz: (other.z - self.z) / time,
-        }
-    }
-    fn position_at(&self, time: i128) -> Self {
-        Hailstone {
-            x: self.dx * time + self.x,
-            y: self.dy * time + self.y,
-            z: self.dz * time + self.z,
-            ..*self
-        }
-    }
-}
+type Hailstone = Line;
 
 pub fn part_one(input: &str) -> Option<usize> {
     Some(intersections_2d(
         &parse(input),
-        200000000000000f64..=400000000000000f64,
+        200000000000000..=400000000000000,
     ))
 }
 
-pub fn intersections_2d(stones: &[Hailstone], test_range: RangeInclusive<f64>) -> usize {
+pub fn intersections_2d(stones: &[Hailstone], test_range: RangeInclusive<i128>) -> usize {
+    let (low, high) = (
+        Rational::from_integer(*test_range.start()),
+        Rational::from_integer(*test_range.end()),
+    );
     stones
         .iter()
         .tuple_combinations()
         .filter_map(|(a, b)| intersection2d(a, b))
-        .filter(|(x, y)| test_range.contains(x) && test_range.contains(y))
+        .filter(|point| (low..=high).contains(&point.x) && (low..=high).contains(&point.y))
         .count()
 }
 
-fn intersection2d(a: &Hailstone, b: &Hailstone) -> Option<(f64, f64)> {
-    let (x1, y1, dx1, dy1) = (a.x as f64, a.y as f64, a.dx as f64, a.dy as f64);
-    let (x2, y2, dx2, dy2) = (b.x as f64, b.y as f64, b.dx as f64, b.dy as f64);
-    if dx1 * dy2 - dy1 * dx2 == 0f64 {
-        None
-    } else {
-        let m1 = dy1 / dx1;
-        let q1 = -m1 * x1 + y1;
-        let m2 = dy2 / dx2;
-        let q2 = -m2 * x2 + y2;
-
-        let xc = (q2 - q1) / (m1 - m2);
-        let yc = m1 * xc + q1;
-
-        let tc1 = (xc - x1) / dx1;
-        let tc2 = (xc - x2) / dx2;
-        if tc1 < 0f64 || tc2 < 0f64 {
-            None
-        } else {
-            Some((xc, yc))
-        }
+/// Where the future paths of two hailstones cross, ignoring the `z` axis.
+fn intersection2d(a: &Hailstone, b: &Hailstone) -> Option<Vec3<Rational>> {
+    let (t, s) = a.intersect_xy(b)?;
+    match t < Rational::from_integer(0) || s < Rational::from_integer(0) {
+        true => None,
+        false => Some(a.point_at(t)),
     }
 }
 
+/// Rock that hits every hailstone
+///
+/// A rock thrown from P with velocity V hits hailstone i when it is at the same position
+/// at the same time, so P - pᵢ and V - vᵢ are parallel:
+///
+///     (P - pᵢ) × (V - vᵢ) = 0
+///
+/// The P × V term is shared by every hailstone, so subtracting the equations of two stones
+/// leaves three equations that are linear in P and V:
+///
+///     P × (vⱼ - vᵢ) + (pⱼ - pᵢ) × V = pⱼ × vⱼ - pᵢ × vᵢ
 pub fn part_two(input: &str) -> Option<i128> {
     let stones = parse(input);
-    let [a, b, c] = stones[..3] else {
-        panic!("Method requires three stones")
-    };
-    let t1 = intersection_time(&[a, b, c]);
-    let t2 = intersection_time(&[b, a, c]);
-    Some(
-        [(t1, a), (t2, b)]
-            .into_iter()
-            .map(|(time, stone)| stone.position_at(time))
-            .tuples()
-            .map(|(collision_1, collision_2)| {
-                collision_1
-                    .velocity_delta(&collision_2, t2 - t1)
-                    .position_at(t1)
-            })
-            .map(|delta| a.position_at(t1).sub(delta))
-            .map(|s| s.x + s.y + s.z)
-            .next()
-            .unwrap(),
-    )
+    let rock = throw(&stones)?;
+    Some(rock.origin.x + rock.origin.y + rock.origin.z)
 }
-fn intersection_time(stones: &[Hailstone; 3]) -> i128 {
-    let (x1, y1, z1, dx1, dy1, dz1) = stones[0].into();
-    let (x2, y2, z2, dx2, dy2, dz2) = stones[1].into();
-    let (x3, y3, z3, dx3, dy3, dz3) = stones[2].into();
-    let yz = y1 * (z2 - z3) + y2 * (-z1 + z3) + y3 * (z1 - z2);
-    let xz = x1 * (-z2 + z3) + x2 * (z1 - z3) + x3 * (-z1 + z2);
-    let xy = x1 * (y2 - y3) + x2 * (-y1 + y3) + x3 * (y1 - y2);
-    let dxdy = dx1 * (dy2 - dy3) + dx2 * (-dy1 + dy3) + dx3 * (dy1 - dy2);
-    let dxdz = dx1 * (-dz2 + dz3) + dx2 * (dz1 - dz3) + dx3 * (-dz1 + dz2);
-    let dydz = dy1 * (dz2 - dz3) + dy2 * (-dz1 + dz3) + dy3 * (dz1 - dz2);
-    ((dx2 - dx3) * yz + (dy2 - dy3) * xz + (dz2 - dz3) * xy)
-        / ((z2 - z3) * dxdy + (y2 - y3) * dxdz + (x2 - x3) * dydz)
+
+fn throw(stones: &[Hailstone]) -> Option<Hailstone> {
+    // The equations of any two stones are the sum of those of the consecutive stones between
+    // them, so consecutive pairs pin the rock down whenever all pairs would, even when the
+    // first few stones are parallel or share a velocity.
+    let (coefficients, constants): (Vec<Vec<BigRational>>, Vec<BigRational>) = stones
+        .iter()
+        .tuple_windows()
+        .flat_map(|(i, j)| {
+            let w = j.direction - i.direction;
+            let u = j.origin - i.origin;
+            let rhs = j.origin.cross(&j.direction) - i.origin.cross(&i.direction);
+            [
+                ([0, w.z, -w.y, 0, -u.z, u.y], rhs.x),
+                ([-w.z, 0, w.x, u.z, 0, -u.x], rhs.y),
+                ([w.y, -w.x, 0, -u.y, u.x, 0], rhs.z),
+            ]
+        })
+        .map(|(row, constant)| (row.map(rational).to_vec(), rational(constant)))
+        .unzip();
+
+    let solution: Vec<i128> = solve(coefficients, constants)?
+        .into_iter()
+        .map(|n| n.is_integer().then(|| n.to_integer().to_i128()).flatten())
+        .collect::<Option<_>>()?;
+    let rock = Line::new(
+        Vec3::new(solution[0], solution[1], solution[2]),
+        Vec3::new(solution[3], solution[4], solution[5]),
+    );
+
+    match stones.iter().all(|stone| hits(&rock, stone)) {
+        true => Some(rock),
+        false => None,
+    }
+}
+
+/// Whether `rock` and `stone` are at the same position at some time t ≥ 0.
+///
+/// Relative to the rock, the stone starts at `offset` and moves along `-approach`,
+/// so they meet when `offset = approach * t`. Moving in parallel at the same speed,
+/// they only meet if they start together.
+fn hits(rock: &Hailstone, stone: &Hailstone) -> bool {
+    let offset = stone.origin - rock.origin;
+    let approach = rock.direction - stone.direction;
+    offset.cross(&approach) == Vec3::default()
+        && offset.dot(&approach) >= 0
+        && (approach != Vec3::default() || offset == Vec3::default())
+}
+
+fn rational(n: i128) -> BigRational {
+    BigRational::from_integer(BigInt::from(n))
 }
 
 mod parse {
     use crate::Hailstone;
-    use nom::branch::alt;
-    use nom::character::complete;
-    use nom::character::complete::{char, line_ending, space0};
+    use advent_of_code::parse::{lines, parse_all, separated_numbers};
+    use advent_of_code::space::Vec3;
+    use nom::character::complete::{char, space0};
     use nom::combinator::map;
-    use nom::multi::separated_list1;
-    use nom::sequence::delimited;
+    use nom::sequence::{delimited, separated_pair};
     use nom::IResult;
 
     pub fn parse(input: &str) -> Vec<Hailstone> {
-        hailstones(input).unwrap().1
-    }
-
-    fn hailstones(input: &str) -> IResult<&str, Vec<Hailstone>> {
-        separated_list1(line_ending, hailstone)(input)
+        parse_all(lines(hailstone), input).unwrap()
     }
 
     fn hailstone(input: &str) -> IResult<&str, Hailstone> {
         map(
-            separated_list1(
-                delimited(space0, alt((char(','), char('@'))), space0),
-                complete::i128,
+            separated_pair(
+                separated_numbers(','),
+                delimited(space0, char('@'), space0),
+                separated_numbers(','),
             ),
-            |list| Hailstone::from((list[0], list[1], list[2], list[3], list[4], list[5])),
+            |(p, v)| Hailstone::new(Vec3::new(p[0], p[1], p[2]), Vec3::new(v[0], v[1], v[2])),
         )(input)
     }
 }
@@ -183,7 +138,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let stones = parse(&advent_of_code::template::read_file("examples", DAY));
-        let result = intersections_2d(&stones, 7f64..=27f64);
+        let result = intersections_2d(&stones, 7..=27);
         assert_eq!(result, 2);
     }
 
@@ -192,4 +147,31 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(47));
     }
+
+    #[test]
+    fn test_hits() {
+        let rock = Hailstone::new(Vec3::new(24, 13, 10), Vec3::new(-3, 1, 2));
+        let stone = |origin: [i128; 3], direction: [i128; 3]| {
+            Hailstone::new(Vec3::from(origin), Vec3::from(direction))
+        };
+        assert!(hits(&rock, &stone([19, 13, 30], [-2, 1, -2])));
+        assert!(!hits(&rock, &stone([25, 13, 10], [-3, 1, 2])));
+        assert!(hits(&rock, &stone([24, 13, 10], [-3, 1, 2])));
+        assert!(!hits(&rock, &stone([25, 13, 6], [-2, 1, -2])));
+    }
+
+    #[test]
+    fn throws_past_degenerate_stones() {
+        let rock = Hailstone::new(Vec3::new(24, 13, 10), Vec3::new(-3, 1, 2));
+        let velocity = Vec3::new(1, 1, 1);
+        // Three stones sharing a velocity, each hit by the rock at time t.
+        let degenerate = (1..=3)
+            .map(|t| Hailstone::new(rock.origin + (rock.direction - velocity) * t, velocity))
+            .collect_vec();
+        assert_eq!(throw(&degenerate), None);
+
+        let stones = parse(&advent_of_code::template::read_file("examples", DAY));
+        let stones = [degenerate, stones].concat();
+        assert_eq!(throw(&stones), Some(rock));
+    }
 }
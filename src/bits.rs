@@ -0,0 +1,649 @@
+use std::fmt::{Debug, Display};
+use std::ops::{BitAnd, BitOr, BitXor, Not, Range, Shl, Shr};
+
+/// A set of small integers below `64 * WORDS`, stored inline as bits.
+///
+/// Cheap to copy, compare and hash, so it works well as search state.
+///
+/// ```
+/// # use advent_of_code::bits::BitSet;
+/// let mut set = BitSet::<2>::new();
+/// set.insert(3);
+/// set.insert(100);
+/// assert_eq!((set << 1).iter().collect::<Vec<_>>(), vec![4, 101]);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitSet<const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    pub const CAPACITY: usize = 64 * WORDS;
+
+    pub const fn new() -> Self {
+        Self { words: [0; WORDS] }
+    }
+
+    /// Adds `index`, returning whether it was new.
+    pub fn insert(&mut self, index: usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        let new = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        new
+    }
+
+    /// Removes `index`, returning whether it was present.
+    pub fn remove(&mut self, index: usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        present
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        self.words[word] & mask != 0
+    }
+
+    /// The number of members.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Members in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        ones(&self.words)
+    }
+
+    fn locate(index: usize) -> (usize, u64) {
+        assert!(
+            index < Self::CAPACITY,
+            "{index} does not fit in a BitSet of {} bits",
+            Self::CAPACITY
+        );
+        (index / 64, 1 << (index % 64))
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for index in iter {
+            set.insert(index);
+        }
+        set
+    }
+}
+
+impl<const WORDS: usize> Debug for BitSet<WORDS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+macro_rules! bitset_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl<const WORDS: usize> $trait for BitSet<WORDS> {
+            type Output = Self;
+
+            fn $method(mut self, rhs: Self) -> Self {
+                for (word, other) in self.words.iter_mut().zip(rhs.words) {
+                    *word $op other;
+                }
+                self
+            }
+        }
+    };
+}
+
+bitset_op!(BitAnd, bitand, &=);
+bitset_op!(BitOr, bitor, |=);
+bitset_op!(BitXor, bitxor, ^=);
+
+impl<const WORDS: usize> Not for BitSet<WORDS> {
+    type Output = Self;
+
+    fn not(mut self) -> Self {
+        self.words.iter_mut().for_each(|word| *word = !*word);
+        self
+    }
+}
+
+/// Moves every member up by `shift`, dropping those that no longer fit.
+impl<const WORDS: usize> Shl<usize> for BitSet<WORDS> {
+    type Output = Self;
+
+    fn shl(mut self, shift: usize) -> Self {
+        shift_up(&mut self.words, shift);
+        self
+    }
+}
+
+/// Moves every member down by `shift`, dropping those that would go below zero.
+impl<const WORDS: usize> Shr<usize> for BitSet<WORDS> {
+    type Output = Self;
+
+    fn shr(mut self, shift: usize) -> Self {
+        shift_down(&mut self.words, shift);
+        self
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular grid of on/off cells, one bit per cell.
+///
+/// Each row is a run of whole words, so moving every cell one step in any direction
+/// is a handful of shifts per row rather than a lookup per cell.
+///
+/// ```
+/// # use advent_of_code::bits::BitGrid;
+/// let open = BitGrid::parse(".#.\n...\n", |c| c == '.');
+/// let mut reached = BitGrid::new(3, 2);
+/// reached.insert(0, 0);
+/// let reached = reached.neighbours() & &open;
+/// assert_eq!(reached.iter().collect::<Vec<_>>(), vec![(1, 0)]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    /// Words per row.
+    stride: usize,
+    /// Row-major, with the unused high bits of each row's last word always clear.
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// An empty grid.
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(64);
+        Self {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * height],
+        }
+    }
+
+    /// A grid with a row per line, setting the cells whose character satisfies `set`.
+    ///
+    /// The width is that of the longest line.
+    pub fn parse(input: &str, set: impl Fn(char) -> bool) -> Self {
+        let width = input.lines().map(|line| line.chars().count()).max();
+        let mut grid = Self::new(width.unwrap_or(0), input.lines().count());
+        for (row, line) in input.lines().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                if set(ch) {
+                    grid.insert(row, col);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Sets the cell, returning whether it was clear.
+    pub fn insert(&mut self, row: usize, col: usize) -> bool {
+        let (word, mask) = self.locate(row, col);
+        let new = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        new
+    }
+
+    /// Clears the cell, returning whether it was set.
+    pub fn remove(&mut self, row: usize, col: usize) -> bool {
+        let (word, mask) = self.locate(row, col);
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        present
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        let (word, mask) = self.locate(row, col);
+        self.words[word] & mask != 0
+    }
+
+    /// The number of set cells.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Set cells as `(row, col)`, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.rows()
+            .enumerate()
+            .flat_map(|(row, words)| ones(words).map(move |col| (row, col)))
+    }
+
+    /// The number of set cells in `cols` of `row`, counted a word at a time.
+    pub fn count_in_row(&self, row: usize, cols: Range<usize>) -> usize {
+        let offset = self.row_offset(row, &cols);
+        masks(cols)
+            .map(|(index, mask)| (self.words[offset + index] & mask).count_ones() as usize)
+            .sum()
+    }
+
+    /// Sets or clears all of `cols` in `row`, a word at a time.
+    pub fn set_in_row(&mut self, row: usize, cols: Range<usize>, value: bool) {
+        let offset = self.row_offset(row, &cols);
+        for (index, mask) in masks(cols) {
+            match value {
+                true => self.words[offset + index] |= mask,
+                false => self.words[offset + index] &= !mask,
+            }
+        }
+    }
+
+    /// The grid mirrored along its main diagonal, so rows become columns. Works on 64x64 blocks
+    /// of words rather than single cells.
+    ///
+    /// ```
+    /// # use advent_of_code::bits::BitGrid;
+    /// let grid = BitGrid::parse("##.\n..#\n", |c| c == '#');
+    /// assert_eq!(grid.transpose().to_string(), "#.\n#.\n.#\n");
+    /// ```
+    pub fn transpose(&self) -> Self {
+        let mut grid = Self::new(self.height, self.width);
+        let mut block = [0; 64];
+        for block_row in 0..self.height.div_ceil(64) {
+            for block_col in 0..self.stride {
+                for (i, word) in block.iter_mut().enumerate() {
+                    let row = block_row * 64 + i;
+                    *word = match row < self.height {
+                        true => self.words[row * self.stride + block_col],
+                        false => 0,
+                    };
+                }
+                transpose_block(&mut block);
+                for (i, &word) in block.iter().enumerate() {
+                    let row = block_col * 64 + i;
+                    if row < grid.height {
+                        grid.words[row * grid.stride + block_row] = word;
+                    }
+                }
+            }
+        }
+        grid
+    }
+
+    /// The set cells of each row as words, lowest column in the lowest bit.
+    pub fn rows(&self) -> impl Iterator<Item = &[u64]> {
+        // `max(1)` keeps `chunks` happy for zero-width grids, which have no words anyway.
+        self.words.chunks(self.stride.max(1))
+    }
+
+    /// Every set cell moved one row up; the top row falls off.
+    pub fn north(&self) -> Self {
+        let mut grid = Self::new(self.width, self.height);
+        if self.height > 0 {
+            let skip = self.stride;
+            grid.words[..self.words.len() - skip].copy_from_slice(&self.words[skip..]);
+        }
+        grid
+    }
+
+    /// Every set cell moved one row down; the bottom row falls off.
+    pub fn south(&self) -> Self {
+        let mut grid = Self::new(self.width, self.height);
+        if self.height > 0 {
+            let skip = self.stride;
+            grid.words[skip..].copy_from_slice(&self.words[..self.words.len() - skip]);
+        }
+        grid
+    }
+
+    /// Every set cell moved one column right; the last column falls off.
+    pub fn east(&self) -> Self {
+        let mut grid = self.clone();
+        for row in grid.words.chunks_mut(self.stride.max(1)) {
+            shift_up(row, 1);
+        }
+        grid.clear_padding();
+        grid
+    }
+
+    /// Every set cell moved one column left; the first column falls off.
+    pub fn west(&self) -> Self {
+        let mut grid = self.clone();
+        for row in grid.words.chunks_mut(self.stride.max(1)) {
+            shift_down(row, 1);
+        }
+        grid
+    }
+
+    /// The cells orthogonally next to any set cell.
+    pub fn neighbours(&self) -> Self {
+        self.north() | &self.south() | &self.east() | &self.west()
+    }
+
+    /// The index of the first word of `row`, checking that `cols` lie within it.
+    fn row_offset(&self, row: usize, cols: &Range<usize>) -> usize {
+        assert!(
+            row < self.height && cols.end <= self.width,
+            "row {row}, columns {cols:?} are outside a {}x{} BitGrid",
+            self.width,
+            self.height
+        );
+        row * self.stride
+    }
+
+    fn locate(&self, row: usize, col: usize) -> (usize, u64) {
+        assert!(
+            row < self.height && col < self.width,
+            "({row}, {col}) is outside a {}x{} BitGrid",
+            self.width,
+            self.height
+        );
+        (row * self.stride + col / 64, 1 << (col % 64))
+    }
+
+    fn clear_padding(&mut self) {
+        let used = self.width % 64;
+        if used == 0 {
+            return;
+        }
+        let mask = (1 << used) - 1;
+        for row in self.words.chunks_mut(self.stride) {
+            row[self.stride - 1] &= mask;
+        }
+    }
+
+    fn combine(mut self, rhs: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        assert_eq!(
+            (self.width, self.height),
+            (rhs.width, rhs.height),
+            "BitGrids must have the same size"
+        );
+        for (word, &other) in self.words.iter_mut().zip(&rhs.words) {
+            *word = op(*word, other);
+        }
+        self
+    }
+}
+
+impl BitAnd<&BitGrid> for BitGrid {
+    type Output = Self;
+
+    fn bitand(self, rhs: &BitGrid) -> Self {
+        self.combine(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr<&BitGrid> for BitGrid {
+    type Output = Self;
+
+    fn bitor(self, rhs: &BitGrid) -> Self {
+        self.combine(rhs, |a, b| a | b)
+    }
+}
+
+impl BitXor<&BitGrid> for BitGrid {
+    type Output = Self;
+
+    fn bitxor(self, rhs: &BitGrid) -> Self {
+        self.combine(rhs, |a, b| a ^ b)
+    }
+}
+
+impl Not for BitGrid {
+    type Output = Self;
+
+    fn not(mut self) -> Self {
+        self.words.iter_mut().for_each(|word| *word = !*word);
+        self.clear_padding();
+        self
+    }
+}
+
+impl Display for BitGrid {
+    /// `#` for set cells and `.` for clear ones, a line per row.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.height {
+            let line: String = (0..self.width)
+                .map(|col| match self.contains(row, col) {
+                    true => '#',
+                    false => '.',
+                })
+                .collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BitGrid {}x{}\n{self}", self.width, self.height)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The positions of the set bits in `words`, lowest first.
+fn ones(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    words.iter().enumerate().flat_map(|(index, &word)| {
+        let mut rest = word;
+        std::iter::from_fn(move || match rest {
+            0 => None,
+            _ => {
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some(index * 64 + bit)
+            }
+        })
+    })
+}
+
+/// The words covering the bits in `range`, as `(index, mask of the bits in that word)`.
+fn masks(range: Range<usize>) -> impl Iterator<Item = (usize, u64)> {
+    let bits = |from: usize, to: usize| match to - from {
+        64 => u64::MAX,
+        len => ((1 << len) - 1) << from,
+    };
+    let (start, end) = (range.start, range.end.max(range.start));
+    (start / 64..end.div_ceil(64)).map(move |index| {
+        let from = start.max(index * 64) - index * 64;
+        let to = end.min(index * 64 + 64) - index * 64;
+        (index, bits(from, to))
+    })
+}
+
+/// Transposes a 64x64 block of bits in place, swapping ever smaller off-diagonal blocks.
+/// See Hacker's Delight, section 7-3.
+fn transpose_block(block: &mut [u64; 64]) {
+    let mut width = 32;
+    let mut mask: u64 = 0x0000_0000_ffff_ffff;
+    while width != 0 {
+        let mut k = 0;
+        while k < 64 {
+            let swap = ((block[k] >> width) ^ block[k + width]) & mask;
+            block[k] ^= swap << width;
+            block[k + width] ^= swap;
+            k = (k + width + 1) & !width;
+        }
+        width >>= 1;
+        mask ^= mask << width;
+    }
+}
+
+/// Shifts a little-endian run of words towards the high end.
+fn shift_up(words: &mut [u64], shift: usize) {
+    let (whole, part) = (shift / 64, shift % 64);
+    for index in (0..words.len()).rev() {
+        let low = index.checked_sub(whole).map_or(0, |i| words[i]);
+        let carry = match (part, index.checked_sub(whole + 1)) {
+            (1.., Some(i)) => words[i] >> (64 - part),
+            _ => 0,
+        };
+        words[index] = (low << part) | carry;
+    }
+}
+
+/// Shifts a little-endian run of words towards the low end.
+fn shift_down(words: &mut [u64], shift: usize) {
+    let (whole, part) = (shift / 64, shift % 64);
+    let len = words.len();
+    for index in 0..len {
+        let high = words.get(index + whole).copied().unwrap_or(0);
+        let carry = match part {
+            0 => 0,
+            _ => words.get(index + whole + 1).map_or(0, |w| w << (64 - part)),
+        };
+        words[index] = (high >> part) | carry;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BitGrid, BitSet};
+    use std::collections::HashSet;
+
+    #[test]
+    fn tracks_set_members() {
+        let mut set = BitSet::<2>::new();
+        assert!(set.is_empty());
+        assert!(set.insert(5));
+        assert!(!set.insert(5));
+        assert!(set.insert(127));
+        assert!(set.contains(127) && !set.contains(6));
+        assert_eq!(set.len(), 2);
+        assert!(set.remove(5));
+        assert!(!set.remove(5));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![127]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_members_beyond_capacity() {
+        BitSet::<1>::new().insert(64);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a: BitSet<2> = [1, 2, 70].into_iter().collect();
+        let b: BitSet<2> = [2, 3, 70].into_iter().collect();
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), vec![2, 70]);
+        assert_eq!((a | b).iter().collect::<Vec<_>>(), vec![1, 2, 3, 70]);
+        assert_eq!((a ^ b).iter().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!((!a).len(), 125);
+    }
+
+    #[test]
+    fn shifts_sets_across_words() {
+        let set: BitSet<3> = [0, 63, 100, 190].into_iter().collect();
+        assert_eq!((set << 1).iter().collect::<Vec<_>>(), vec![1, 64, 101, 191]);
+        assert_eq!((set << 65).iter().collect::<Vec<_>>(), vec![65, 128, 165]);
+        assert_eq!((set >> 64).iter().collect::<Vec<_>>(), vec![36, 126]);
+        assert_eq!((set >> 1).iter().collect::<Vec<_>>(), vec![62, 99, 189]);
+        assert!((set << 192).is_empty());
+    }
+
+    #[test]
+    fn hashes_sets_by_members() {
+        let sets: HashSet<BitSet<1>> = [[1, 2], [2, 1], [1, 3]]
+            .into_iter()
+            .map(|members| members.into_iter().collect())
+            .collect();
+        assert_eq!(sets.len(), 2);
+    }
+
+    #[test]
+    fn parses_and_prints_grids() {
+        let input = "#..#\n.##.\n";
+        let grid = BitGrid::parse(input, |c| c == '#');
+        assert_eq!((grid.width(), grid.height(), grid.len()), (4, 2, 4));
+        assert!(grid.contains(1, 2) && !grid.contains(1, 3));
+        assert_eq!(grid.to_string(), input);
+        assert_eq!((!grid).to_string(), ".##.\n#..#\n");
+    }
+
+    #[test]
+    fn shifts_grids() {
+        let grid = BitGrid::parse("#..\n.#.\n..#\n", |c| c == '#');
+        assert_eq!(grid.north().to_string(), ".#.\n..#\n...\n");
+        assert_eq!(grid.south().to_string(), "...\n#..\n.#.\n");
+        assert_eq!(grid.east().to_string(), ".#.\n..#\n...\n");
+        assert_eq!(grid.west().to_string(), "...\n#..\n.#.\n");
+    }
+
+    #[test]
+    fn shifts_wide_grids_across_words() {
+        let mut grid = BitGrid::new(130, 2);
+        grid.insert(0, 63);
+        grid.insert(0, 129);
+        grid.insert(1, 64);
+        assert_eq!(
+            grid.east().iter().collect::<Vec<_>>(),
+            vec![(0, 64), (1, 65)]
+        );
+        assert_eq!(
+            grid.west().iter().collect::<Vec<_>>(),
+            vec![(0, 62), (0, 128), (1, 63)]
+        );
+        assert_eq!(grid.neighbours().len(), 6);
+    }
+
+    #[test]
+    fn combines_grids() {
+        let a = BitGrid::parse("##.\n...", |c| c == '#');
+        let b = BitGrid::parse(".##\n..#", |c| c == '#');
+        assert_eq!((a.clone() & &b).to_string(), ".#.\n...\n");
+        assert_eq!((a.clone() | &b).to_string(), "###\n..#\n");
+        assert_eq!((a ^ &b).to_string(), "#.#\n..#\n");
+    }
+
+    #[test]
+    fn counts_and_sets_row_ranges() {
+        let mut grid = BitGrid::new(200, 2);
+        for col in (0..200).step_by(3) {
+            grid.insert(1, col);
+        }
+        assert_eq!(grid.count_in_row(1, 60..130), 24);
+        assert_eq!(grid.count_in_row(1, 64..128), 21);
+        assert_eq!(grid.count_in_row(0, 0..200), 0);
+        grid.set_in_row(0, 62..131, true);
+        assert_eq!(grid.count_in_row(0, 0..200), 69);
+        assert!(grid.contains(0, 62) && grid.contains(0, 130) && !grid.contains(0, 131));
+        grid.set_in_row(1, 0..200, false);
+        grid.set_in_row(0, 70..70, false);
+        assert_eq!(grid.len(), 69);
+    }
+
+    #[test]
+    fn transposes_grids_across_blocks() {
+        let mut grid = BitGrid::new(70, 130);
+        for (row, col) in [(0, 0), (1, 65), (64, 3), (129, 69), (100, 64)] {
+            grid.insert(row, col);
+        }
+        let transposed = grid.transpose();
+        assert_eq!((transposed.width(), transposed.height()), (130, 70));
+        let mut cells: Vec<_> = transposed.iter().map(|(row, col)| (col, row)).collect();
+        cells.sort();
+        assert_eq!(cells, grid.iter().collect::<Vec<_>>());
+        assert_eq!(transposed.transpose(), grid);
+    }
+}
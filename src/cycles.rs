@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A repeating sequence of states: the state after `start` steps recurs every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state equals the state after `step` steps.
+    pub fn earliest_equivalent(&self, step: usize) -> usize {
+        match step < self.start {
+            true => step,
+            false => self.start + (step - self.start) % self.length,
+        }
+    }
+}
+
+/// The state reached after a number of steps, with the cycle if one was found on the way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extrapolation<S> {
+    pub state: S,
+    pub cycle: Option<Cycle>,
+}
+
+/// Applies `step` to `initial` `steps` times, skipping ahead once the states start repeating.
+///
+/// Every state is hashed until either `steps` are done, or a state recurs and the result
+/// can be read from the states seen so far. The returned [`Cycle`] counts `initial` as step `0`.
+///
+/// ```
+/// # use advent_of_code::cycles::{state_at, Cycle};
+/// // 1, 3, 9, 27 % 10 = 7, 21 % 10 = 1, ...
+/// let result = state_at(1, |n| n * 3 % 10, 1_000_000_001);
+/// assert_eq!(result.state, 3);
+/// assert_eq!(result.cycle, Some(Cycle { start: 0, length: 4 }));
+/// ```
+pub fn state_at<S, F>(initial: S, mut step: F, steps: usize) -> Extrapolation<S>
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states: Vec<S> = vec![];
+    let mut state = initial;
+
+    for n in 0..steps {
+        let next = step(&state);
+        seen.insert(state.clone(), n);
+        states.push(state);
+
+        if let Some(&start) = seen.get(&next) {
+            let cycle = Cycle {
+                start,
+                length: n + 1 - start,
+            };
+            return Extrapolation {
+                state: states.swap_remove(cycle.earliest_equivalent(steps)),
+                cycle: Some(cycle),
+            };
+        }
+        state = next;
+    }
+
+    Extrapolation { state, cycle: None }
+}
+
+/// Finds the first cycle in the states reached from `initial`.
+/// Never returns if the sequence of states does not repeat.
+pub fn find_cycle<S, F>(initial: S, step: F) -> Cycle
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    state_at(initial, step, usize::MAX).cycle.unwrap()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_cycle, state_at, Cycle};
+
+    /// 0 → 1 → 2 → 3 → 4 → 2 → ..., a tail of two states before a cycle of three.
+    fn step(n: &usize) -> usize {
+        match n {
+            4 => 2,
+            n => n + 1,
+        }
+    }
+
+    const CYCLE: Cycle = Cycle {
+        start: 2,
+        length: 3,
+    };
+
+    fn expected(steps: usize) -> usize {
+        CYCLE.earliest_equivalent(steps)
+    }
+
+    #[test]
+    fn finds_cycle() {
+        assert_eq!(find_cycle(0, step), CYCLE);
+        assert_eq!(
+            find_cycle(3, step),
+            Cycle {
+                start: 0,
+                length: 3
+            }
+        );
+    }
+
+    #[test]
+    fn matches_simulation() {
+        for steps in 0..20 {
+            let simulated = (0..steps).fold(0, |n, _| step(&n));
+            assert_eq!(simulated, expected(steps));
+            assert_eq!(state_at(0, step, steps).state, simulated, "steps: {steps}");
+        }
+    }
+
+    #[test]
+    fn returns_initial_state_for_zero_steps() {
+        let result = state_at(0, step, 0);
+        assert_eq!(result.state, 0);
+        assert_eq!(result.cycle, None);
+    }
+
+    #[test]
+    fn detects_cycle_on_last_step() {
+        let result = state_at(0, step, 4);
+        assert_eq!(result.state, 4);
+        assert_eq!(result.cycle, None);
+
+        let result = state_at(0, step, 5);
+        assert_eq!(result.state, 2);
+        assert_eq!(result.cycle, Some(CYCLE));
+    }
+
+    #[test]
+    fn extrapolates_far_steps() {
+        for steps in [1_000_000_000, 1_000_000_001, 1_000_000_002, usize::MAX] {
+            assert_eq!(state_at(0, step, steps).state, expected(steps));
+        }
+    }
+
+    #[test]
+    fn handles_fixed_points() {
+        let result = state_at(7, |n: &u32| *n, 1_000_000_000);
+        assert_eq!(result.state, 7);
+        assert_eq!(
+            result.cycle,
+            Some(Cycle {
+                start: 0,
+                length: 1
+            })
+        );
+
+        let result = state_at(0, |n: &u32| (n + 1).min(3), 1_000_000_000);
+        assert_eq!(result.state, 3);
+        assert_eq!(
+            result.cycle,
+            Some(Cycle {
+                start: 3,
+                length: 1
+            })
+        );
+    }
+
+    #[test]
+    fn maps_steps_to_earliest_equivalent() {
+        assert_eq!(CYCLE.earliest_equivalent(1), 1);
+        assert_eq!(CYCLE.earliest_equivalent(2), 2);
+        assert_eq!(CYCLE.earliest_equivalent(4), 4);
+        assert_eq!(CYCLE.earliest_equivalent(5), 2);
+        assert_eq!(CYCLE.earliest_equivalent(9), 3);
+    }
+}
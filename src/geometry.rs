@@ -0,0 +1,230 @@
+use num::{Integer, PrimInt, Signed};
+
+/// Integer type usable as a polygon coordinate.
+pub trait Coordinate: PrimInt + Signed + Integer {}
+impl<T: PrimInt + Signed + Integer> Coordinate for T {}
+
+/// A point on the integer lattice as `(x, y)`.
+pub type Point<T> = (T, T);
+
+/// Orientation of a polygon's vertices, assuming `x` grows to the right and `y` grows upwards.
+/// With screen coordinates, where `y` grows downwards, the two are swapped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// Where a point lies relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Twice the signed area of a simple polygon, positive for counter-clockwise vertices.
+///
+/// Shoelace formula, requires the vertices to be in consecutive order
+/// https://en.wikipedia.org/wiki/Shoelace_formula
+///
+/// ```text
+/// 2A = ∑ ( xᵢ * yᵢ₊₁ - xᵢ₊₁ * yᵢ )
+/// ```
+pub fn signed_double_area<T: Coordinate>(vertices: &[Point<T>]) -> T {
+    edges(vertices).fold(T::zero(), |area, ((x1, y1), (x2, y2))| {
+        area + (x1 * y2 - x2 * y1)
+    })
+}
+
+/// Twice the area of a simple polygon. Doubling keeps the result exact for lattice polygons.
+pub fn double_area<T: Coordinate>(vertices: &[Point<T>]) -> T {
+    signed_double_area(vertices).abs()
+}
+
+/// The area of a simple polygon, rounded down to the nearest integer.
+///
+/// ```
+/// # use advent_of_code::geometry::area;
+/// assert_eq!(area(&[(0, 0), (4, 0), (4, 3), (0, 3)]), 12);
+/// ```
+pub fn area<T: Coordinate>(vertices: &[Point<T>]) -> T {
+    double_area(vertices) / two()
+}
+
+/// The orientation of the vertices, [`None`] for degenerate polygons without area.
+pub fn winding<T: Coordinate>(vertices: &[Point<T>]) -> Option<Winding> {
+    let area = signed_double_area(vertices);
+    match area.is_positive() {
+        true => Some(Winding::CounterClockwise),
+        false if area.is_negative() => Some(Winding::Clockwise),
+        false => None,
+    }
+}
+
+/// The number of lattice points on the edges of the polygon.
+///
+/// Each edge covers `gcd(|dx|, |dy|)` points, counting one of its two end points.
+pub fn boundary_points<T: Coordinate>(vertices: &[Point<T>]) -> T {
+    edges(vertices).fold(T::zero(), |count, ((x1, y1), (x2, y2))| {
+        count + (x2 - x1).abs().gcd(&(y2 - y1).abs())
+    })
+}
+
+/// The number of lattice points strictly inside the polygon.
+///
+/// Pick's theorem, with `B` boundary points
+/// https://en.wikipedia.org/wiki/Pick%27s_theorem
+///
+/// ```text
+/// A = I + B/2 - 1    =>    I = (2A - B + 2) / 2
+/// ```
+pub fn interior_points<T: Coordinate>(vertices: &[Point<T>]) -> T {
+    (double_area(vertices) - boundary_points(vertices) + two()) / two()
+}
+
+/// The number of lattice points inside or on the boundary of the polygon.
+///
+/// ```
+/// # use advent_of_code::geometry::{boundary_points, interior_points, lattice_points};
+/// let square = [(0, 0), (2, 0), (2, 2), (0, 2)];
+/// assert_eq!(interior_points(&square), 1);
+/// assert_eq!(boundary_points(&square), 8);
+/// assert_eq!(lattice_points(&square), 9);
+/// ```
+pub fn lattice_points<T: Coordinate>(vertices: &[Point<T>]) -> T {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+/// Locates `point` relative to a simple polygon using exact ray casting towards positive `x`.
+pub fn locate<T: Coordinate>(vertices: &[Point<T>], point: Point<T>) -> Location {
+    let (px, py) = point;
+    let mut inside = false;
+
+    for ((x1, y1), (x2, y2)) in edges(vertices) {
+        // Positive when the point is left of the edge, zero when it is on the edge's line.
+        let cross = (x2 - x1) * (py - y1) - (px - x1) * (y2 - y1);
+        if cross.is_zero()
+            && x1.min(x2) <= px
+            && px <= x1.max(x2)
+            && y1.min(y2) <= py
+            && py <= y1.max(y2)
+        {
+            return Location::Boundary;
+        }
+        if (y1 > py) != (y2 > py) && cross.is_positive() == (y2 > y1) {
+            inside = !inside;
+        }
+    }
+
+    match inside {
+        true => Location::Inside,
+        false => Location::Outside,
+    }
+}
+
+/// Whether `point` is inside or on the boundary of the polygon.
+pub fn contains<T: Coordinate>(vertices: &[Point<T>], point: Point<T>) -> bool {
+    locate(vertices, point) != Location::Outside
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Consecutive pairs of vertices, closing the polygon from the last vertex back to the first.
+fn edges<T: Coordinate>(vertices: &[Point<T>]) -> impl Iterator<Item = (Point<T>, Point<T>)> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+fn two<T: Coordinate>() -> T {
+    T::one() + T::one()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        area, boundary_points, contains, double_area, interior_points, lattice_points, locate,
+        winding, Location, Winding,
+    };
+
+    /// An L-shape: a 4x4 square with its top right 2x2 quadrant cut out.
+    const L_SHAPE: [(i64, i64); 6] = [(0, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)];
+
+    #[test]
+    fn computes_area() {
+        assert_eq!(area(&L_SHAPE), 12);
+        assert_eq!(double_area(&[(0, 0), (3, 0), (0, 1)]), 3);
+        assert_eq!(area(&[(0, 0), (3, 0), (0, 1)]), 1);
+    }
+
+    #[test]
+    fn area_ignores_winding() {
+        let reversed: Vec<_> = L_SHAPE.iter().rev().copied().collect();
+        assert_eq!(area(&reversed), area(&L_SHAPE));
+    }
+
+    #[test]
+    fn detects_winding() {
+        assert_eq!(winding(&L_SHAPE), Some(Winding::CounterClockwise));
+        let reversed: Vec<_> = L_SHAPE.iter().rev().copied().collect();
+        assert_eq!(winding(&reversed), Some(Winding::Clockwise));
+        assert_eq!(winding(&[(0, 0), (1, 1), (2, 2)]), None);
+    }
+
+    #[test]
+    fn counts_lattice_points() {
+        assert_eq!(boundary_points(&L_SHAPE), 16);
+        assert_eq!(interior_points(&L_SHAPE), 5);
+        assert_eq!(lattice_points(&L_SHAPE), 21);
+    }
+
+    #[test]
+    fn counts_diagonal_boundaries() {
+        let triangle = [(0, 0), (4, 0), (0, 4)];
+        assert_eq!(boundary_points(&triangle), 12);
+        assert_eq!(interior_points(&triangle), 3);
+    }
+
+    #[test]
+    fn counts_collinear_vertices() {
+        let square = [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+        ];
+        assert_eq!(boundary_points(&square), 8);
+        assert_eq!(interior_points(&square), 1);
+    }
+
+    #[test]
+    fn locates_points() {
+        assert_eq!(locate(&L_SHAPE, (1, 1)), Location::Inside);
+        assert_eq!(locate(&L_SHAPE, (1, 3)), Location::Inside);
+        assert_eq!(locate(&L_SHAPE, (3, 3)), Location::Outside);
+        assert_eq!(locate(&L_SHAPE, (3, 2)), Location::Boundary);
+        assert_eq!(locate(&L_SHAPE, (4, 0)), Location::Boundary);
+        assert_eq!(locate(&L_SHAPE, (-1, 2)), Location::Outside);
+        assert_eq!(locate(&L_SHAPE, (5, 2)), Location::Outside);
+    }
+
+    #[test]
+    fn locates_points_level_with_vertices() {
+        let diamond = [(2, 0), (4, 2), (2, 4), (0, 2)];
+        assert!(contains(&diamond, (2, 2)));
+        assert!(contains(&diamond, (1, 2)));
+        assert!(!contains(&diamond, (-1, 2)));
+        assert!(!contains(&diamond, (5, 2)));
+        assert_eq!(locate(&diamond, (0, 2)), Location::Boundary);
+    }
+}
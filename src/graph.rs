@@ -0,0 +1,391 @@
+use crate::parse::{key_value, lines, parse_all, ParseError};
+use nom::character::complete::{alphanumeric1, space1};
+use nom::multi::separated_list1;
+use rustworkx_core::connectivity::{connected_components, stoer_wagner_min_cut};
+use rustworkx_core::petgraph::algo::{tarjan_scc, toposort};
+use rustworkx_core::petgraph::graph::{Graph, NodeIndex};
+use rustworkx_core::petgraph::visit::EdgeRef;
+use rustworkx_core::petgraph::{Directed, EdgeType, Incoming, Undirected};
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::fmt::{Display, Write};
+use std::hash::Hash;
+
+/// A graph whose nodes are looked up by label rather than by index.
+///
+/// A thin layer over a petgraph [`Graph`], which stays reachable through [`graph`](Self::graph)
+/// for anything not covered here.
+#[derive(Debug, Clone)]
+pub struct LabelledGraph<L, Ty: EdgeType> {
+    graph: Graph<L, (), Ty>,
+    indices: HashMap<L, NodeIndex>,
+}
+
+pub type UndirectedGraph<L> = LabelledGraph<L, Undirected>;
+pub type DirectedGraph<L> = LabelledGraph<L, Directed>;
+
+impl<L: Hash + Eq + Clone, Ty: EdgeType> Default for LabelledGraph<L, Ty> {
+    fn default() -> Self {
+        Self {
+            graph: Graph::default(),
+            indices: HashMap::new(),
+        }
+    }
+}
+
+impl<L: Hash + Eq + Clone, Ty: EdgeType> LabelledGraph<L, Ty> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A graph with an edge for each pair, adding nodes as they are first mentioned.
+    ///
+    /// ```
+    /// # use advent_of_code::graph::DirectedGraph;
+    /// let graph = DirectedGraph::from_edges([("a", "b"), ("b", "c"), ("a", "c")]);
+    /// assert_eq!(graph.topological_order(), Ok(vec!["a", "b", "c"]));
+    /// ```
+    pub fn from_edges(edges: impl IntoIterator<Item = (L, L)>) -> Self {
+        let mut graph = Self::new();
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    /// The index of the node labelled `label`, adding it if needed.
+    pub fn add_node(&mut self, label: L) -> NodeIndex {
+        match self.indices.get(&label) {
+            Some(&index) => index,
+            None => {
+                let index = self.graph.add_node(label.clone());
+                self.indices.insert(label, index);
+                index
+            }
+        }
+    }
+
+    /// Connects `from` to `to`, adding either node if needed. Adding an edge twice has no effect.
+    pub fn add_edge(&mut self, from: L, to: L) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.graph.update_edge(from, to, ());
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    pub fn index(&self, label: &L) -> Option<NodeIndex> {
+        self.indices.get(label).copied()
+    }
+
+    pub fn label(&self, index: NodeIndex) -> &L {
+        &self.graph[index]
+    }
+
+    /// Node labels in the order they were added.
+    pub fn labels(&self) -> impl Iterator<Item = &L> {
+        self.graph.node_weights()
+    }
+
+    /// Edges as label pairs, in the order they were added.
+    pub fn edges(&self) -> impl Iterator<Item = (&L, &L)> {
+        self.graph
+            .edge_references()
+            .map(|edge| (self.label(edge.source()), self.label(edge.target())))
+    }
+
+    /// The nodes `label` has an edge to. For directed graphs, only outgoing edges count.
+    pub fn neighbours(&self, label: &L) -> impl Iterator<Item = &L> {
+        self.index(label)
+            .into_iter()
+            .flat_map(|index| self.graph.neighbors(index))
+            .map(|index| self.label(index))
+    }
+
+    /// The underlying petgraph graph.
+    pub fn graph(&self) -> &Graph<L, (), Ty> {
+        &self.graph
+    }
+
+    /// Groups of nodes connected to each other, ignoring edge direction.
+    ///
+    /// Each group lists its nodes in the order they were added.
+    pub fn components(&self) -> Vec<Vec<L>> {
+        connected_components(&self.graph)
+            .into_iter()
+            .map(|component| self.sorted_labels(component))
+            .collect()
+    }
+
+    fn sorted_labels(&self, indices: impl IntoIterator<Item = NodeIndex>) -> Vec<L> {
+        let mut indices: Vec<NodeIndex> = indices.into_iter().collect();
+        indices.sort_unstable();
+        indices
+            .into_iter()
+            .map(|index| self.label(index).clone())
+            .collect()
+    }
+
+    /// The graph in Graphviz DOT format, with every node labelled.
+    pub fn to_dot(&self) -> String
+    where
+        L: Display,
+    {
+        self.to_dot_with(|_| String::new(), &[])
+    }
+
+    /// Like [`to_dot`](Self::to_dot), with extra attributes for each node (such as
+    /// `shape=box`, or empty for none) and clusters of nodes drawn together under a title.
+    ///
+    /// ```
+    /// # use advent_of_code::graph::DirectedGraph;
+    /// let graph = DirectedGraph::from_edges([("a", "b")]);
+    /// let dot = graph.to_dot_with(|_| "shape=box".into(), &[("pair".into(), vec!["a", "b"])]);
+    /// assert!(dot.contains("subgraph cluster_0 {\n        label=\"pair\";\n        \"a\" [shape=box];"));
+    /// ```
+    pub fn to_dot_with(
+        &self,
+        attributes: impl Fn(&L) -> String,
+        clusters: &[(String, Vec<L>)],
+    ) -> String
+    where
+        L: Display,
+    {
+        let (kind, arrow) = match Ty::is_directed() {
+            true => ("digraph", "->"),
+            false => ("graph", "--"),
+        };
+        let quote = |text: &str| format!("{text:?}");
+        let node = |label: &L| match attributes(label) {
+            extra if extra.is_empty() => format!("{};", quote(&label.to_string())),
+            extra => format!("{} [{extra}];", quote(&label.to_string())),
+        };
+
+        let mut dot = format!("{kind} {{\n");
+        let mut clustered = HashSet::new();
+        for (index, (title, members)) in clusters.iter().enumerate() {
+            writeln!(dot, "    subgraph cluster_{index} {{").unwrap();
+            writeln!(dot, "        label={};", quote(title)).unwrap();
+            for label in members {
+                writeln!(dot, "        {}", node(label)).unwrap();
+                clustered.insert(label);
+            }
+            dot.push_str("    }\n");
+        }
+        for label in self.labels().filter(|label| !clustered.contains(label)) {
+            writeln!(dot, "    {}", node(label)).unwrap();
+        }
+        for (from, to) in self.edges() {
+            let (from, to) = (quote(&from.to_string()), quote(&to.to_string()));
+            writeln!(dot, "    {from} {arrow} {to};").unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<'a, Ty: EdgeType> LabelledGraph<&'a str, Ty> {
+    /// Parses lines like `jqt: rhn xhk nvd`, with an edge from the node before the colon
+    /// to each node after it.
+    ///
+    /// ```
+    /// # use advent_of_code::graph::UndirectedGraph;
+    /// let graph = UndirectedGraph::from_adjacency("a: b c\nc: d").unwrap();
+    /// assert_eq!((graph.node_count(), graph.edge_count()), (4, 3));
+    /// ```
+    pub fn from_adjacency(input: &'a str) -> Result<Self, ParseError> {
+        let adjacency = parse_all(
+            lines(key_value(
+                alphanumeric1,
+                separated_list1(space1, alphanumeric1),
+            )),
+            input,
+        )?;
+        Ok(Self::from_edges(adjacency.into_iter().flat_map(
+            |(from, targets)| targets.into_iter().map(move |to| (from, to)),
+        )))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A smallest set of edges whose removal splits a graph in two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut<L> {
+    /// The nodes on either side, each in the order they were added.
+    pub sides: (Vec<L>, Vec<L>),
+    /// The removed edges, each from the first side to the second.
+    pub edges: Vec<(L, L)>,
+}
+
+impl<L: Hash + Eq + Clone> UndirectedGraph<L> {
+    /// The minimum cut of the graph, counting every edge once (Stoer-Wagner).
+    ///
+    /// [`None`] if the graph has fewer than two nodes.
+    pub fn min_cut(&self) -> Option<Cut<L>> {
+        let (_, side) = stoer_wagner_min_cut(&self.graph, |_| Ok::<usize, Infallible>(1))
+            .unwrap_or_else(|e| match e {})?;
+        let side: HashSet<NodeIndex> = side.into_iter().collect();
+        let (first, second): (Vec<NodeIndex>, Vec<NodeIndex>) = self
+            .graph
+            .node_indices()
+            .partition(|index| side.contains(index));
+
+        let edges = self
+            .graph
+            .edge_references()
+            .filter(|edge| side.contains(&edge.source()) != side.contains(&edge.target()))
+            .map(|edge| match side.contains(&edge.source()) {
+                true => (edge.source(), edge.target()),
+                false => (edge.target(), edge.source()),
+            })
+            .map(|(from, to)| (self.label(from).clone(), self.label(to).clone()))
+            .collect();
+
+        Some(Cut {
+            sides: (self.sorted_labels(first), self.sorted_labels(second)),
+            edges,
+        })
+    }
+}
+
+impl<L: Hash + Eq + Clone> DirectedGraph<L> {
+    /// Every node after all of its predecessors, or `Err` with a node on a cycle.
+    pub fn topological_order(&self) -> Result<Vec<L>, L> {
+        toposort(&self.graph, None)
+            .map(|order| {
+                order
+                    .into_iter()
+                    .map(|index| self.label(index).clone())
+                    .collect()
+            })
+            .map_err(|cycle| self.label(cycle.node_id()).clone())
+    }
+
+    /// Groups of nodes that can all reach each other.
+    ///
+    /// Groups come in reverse topological order: no group has an edge to a later one.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<L>> {
+        tarjan_scc(&self.graph)
+            .into_iter()
+            .map(|component| self.sorted_labels(component))
+            .collect()
+    }
+
+    /// The nodes with an edge to `label`.
+    pub fn predecessors(&self, label: &L) -> impl Iterator<Item = &L> {
+        self.index(label)
+            .into_iter()
+            .flat_map(|index| self.graph.neighbors_directed(index, Incoming))
+            .map(|index| self.label(index))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cut, DirectedGraph, UndirectedGraph};
+
+    #[test]
+    fn looks_up_nodes_by_label() {
+        let mut graph = UndirectedGraph::new();
+        graph.add_edge("a", "b");
+        graph.add_edge("b", "a");
+        graph.add_edge("b", "c");
+        assert_eq!((graph.node_count(), graph.edge_count()), (3, 2));
+        assert_eq!(graph.index(&"c").map(|i| *graph.label(i)), Some("c"));
+        assert_eq!(graph.index(&"d"), None);
+
+        let mut neighbours: Vec<_> = graph.neighbours(&"b").copied().collect();
+        neighbours.sort_unstable();
+        assert_eq!(neighbours, vec!["a", "c"]);
+    }
+
+    #[test]
+    fn finds_components() {
+        let mut graph = UndirectedGraph::from_edges([(1, 2), (3, 4), (2, 5)]);
+        graph.add_node(6);
+        assert_eq!(graph.components(), vec![vec![1, 2, 5], vec![3, 4], vec![6]]);
+
+        let directed = DirectedGraph::from_edges([(1, 2), (3, 2)]);
+        assert_eq!(directed.components(), vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn finds_minimum_cuts() {
+        // Two squares joined by a single edge.
+        let graph = UndirectedGraph::from_adjacency("a: b d\nc: b d\ne: f h b\ng: f h").unwrap();
+        let Cut { sides, edges } = graph.min_cut().unwrap();
+
+        let (mut small, mut large) = sides;
+        small.sort_unstable();
+        large.sort_unstable();
+        let (left, right) = match small.contains(&"a") {
+            true => (small, large),
+            false => (large, small),
+        };
+        assert_eq!(left, vec!["a", "b", "c", "d"]);
+        assert_eq!(right, vec!["e", "f", "g", "h"]);
+        assert_eq!(edges.len(), 1);
+        assert!(edges == vec![("e", "b")] || edges == vec![("b", "e")]);
+
+        assert_eq!(UndirectedGraph::<&str>::new().min_cut(), None);
+    }
+
+    #[test]
+    fn orders_directed_graphs() {
+        let graph = DirectedGraph::from_edges([("b", "c"), ("a", "b"), ("c", "d"), ("a", "d")]);
+        assert_eq!(graph.topological_order(), Ok(vec!["a", "b", "c", "d"]));
+        assert_eq!(graph.predecessors(&"d").count(), 2);
+
+        let cyclic = DirectedGraph::from_edges([("a", "b"), ("b", "c"), ("c", "b")]);
+        assert!(matches!(cyclic.topological_order(), Err("b" | "c")));
+    }
+
+    #[test]
+    fn finds_strongly_connected_components() {
+        let graph = DirectedGraph::from_edges([(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 4)]);
+        assert_eq!(
+            graph.strongly_connected_components(),
+            vec![vec![4, 5], vec![1, 2, 3]]
+        );
+    }
+
+    #[test]
+    fn exports_dot() {
+        let graph = DirectedGraph::from_edges([("a", "b\"c")]);
+        assert_eq!(
+            graph.to_dot(),
+            "digraph {\n    \"a\";\n    \"b\\\"c\";\n    \"a\" -> \"b\\\"c\";\n}\n"
+        );
+        let graph = UndirectedGraph::from_edges([(1, 2), (2, 3)]);
+        assert_eq!(
+            graph.to_dot_with(
+                |n| match n {
+                    1 => "color=red".to_string(),
+                    _ => String::new(),
+                },
+                &[("high".to_string(), vec![2, 3])]
+            ),
+            "graph {\n    subgraph cluster_0 {\n        label=\"high\";\n        \"2\";\n        \"3\";\n    }\n    \"1\" [color=red];\n    \"1\" -- \"2\";\n    \"2\" -- \"3\";\n}\n"
+        );
+        let graph = UndirectedGraph::from_edges([(1, 2)]);
+        assert_eq!(
+            graph.to_dot(),
+            "graph {\n    \"1\";\n    \"2\";\n    \"1\" -- \"2\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_adjacency() {
+        assert!(UndirectedGraph::from_adjacency("a: b\nc d").is_err());
+    }
+}
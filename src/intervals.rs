@@ -0,0 +1,574 @@
+use num::PrimInt;
+use std::ops::RangeInclusive;
+
+/// A non-empty, inclusive range of integers.
+///
+/// ```
+/// # use advent_of_code::intervals::Interval;
+/// let interval = Interval::new(3, 7).unwrap();
+/// assert_eq!(interval.size(), 5);
+/// assert_eq!(Interval::new(7, 3), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    /// Creates an [`Interval`] from `start` to `end` (inclusive), returns [`None`] if it would be empty.
+    pub fn new(start: T, end: T) -> Option<Self> {
+        match start <= end {
+            true => Some(Self { start, end }),
+            false => None,
+        }
+    }
+
+    /// Creates an [`Interval`] of `len` values beginning at `start`, returns [`None`] if `len` is zero.
+    pub fn from_len(start: T, len: T) -> Option<Self> {
+        match len > T::zero() {
+            true => Some(Self {
+                start,
+                end: start + (len - T::one()),
+            }),
+            false => None,
+        }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// The number of values in the interval.
+    pub fn size(&self) -> T {
+        self.end - self.start + T::one()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Splits the interval into the values below `point` and the values from `point` upwards.
+    pub fn split_at(&self, point: T) -> (Option<Self>, Option<Self>) {
+        if point <= self.start {
+            (None, Some(*self))
+        } else if point > self.end {
+            (Some(*self), None)
+        } else {
+            (
+                Some(Self {
+                    start: self.start,
+                    end: point - T::one(),
+                }),
+                Some(Self {
+                    start: point,
+                    end: self.end,
+                }),
+            )
+        }
+    }
+
+    /// Moves the interval by `offset`.
+    pub fn shift(&self, offset: T) -> Self {
+        Self {
+            start: self.start + offset,
+            end: self.end + offset,
+        }
+    }
+
+    /// Whether no value lies between the two intervals, i.e. they could be merged into one.
+    fn touches(&self, other: &Self) -> bool {
+        let (first, second) = match self.start <= other.start {
+            true => (self, other),
+            false => (other, self),
+        };
+        first
+            .end
+            .checked_add(&T::one())
+            .is_none_or(|next| next >= second.start)
+    }
+}
+
+impl<T: PrimInt> From<Interval<T>> for RangeInclusive<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..=interval.end
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of integers stored as sorted, disjoint intervals.
+///
+/// ```
+/// # use advent_of_code::intervals::{Interval, IntervalSet};
+/// let a = IntervalSet::from(Interval::new(1, 10).unwrap());
+/// let b = IntervalSet::from(Interval::new(4, 6).unwrap());
+/// assert_eq!(a.difference(&b).size(), 7);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    // sorted by start, neither overlapping nor adjacent.
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    /// The intervals of the set in ascending order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of values in the set.
+    pub fn size(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |acc, interval| acc + interval.size())
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(Interval::start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().map(Interval::end)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end < value);
+        self.intervals.get(idx).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let idx = self.intervals.partition_point(|i| i.start < interval.start);
+        self.intervals.insert(idx, interval);
+        self.intervals = merge(std::mem::take(&mut self.intervals));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut intervals = [self.intervals.as_slice(), other.intervals.as_slice()].concat();
+        intervals.sort_unstable();
+        Self {
+            intervals: merge(intervals),
+        }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (&self.intervals[i], &other.intervals[j]);
+            if let Some(overlap) = a.intersection(b) {
+                intervals.push(overlap);
+            }
+            match a.end < b.end {
+                true => i += 1,
+                false => j += 1,
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// The values of `self` that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let mut j = 0;
+
+        for interval in &self.intervals {
+            while j < other.intervals.len() && other.intervals[j].end < interval.start {
+                j += 1;
+            }
+
+            let mut remaining = Some(*interval);
+            for cut in other.intervals[j..]
+                .iter()
+                .take_while(|cut| cut.start <= interval.end)
+            {
+                let Some(rest) = remaining else { break };
+                let (below, _) = rest.split_at(cut.start);
+                intervals.extend(below);
+                remaining = cut
+                    .end
+                    .checked_add(&T::one())
+                    .and_then(|after| rest.split_at(after).1);
+            }
+            intervals.extend(remaining);
+        }
+
+        Self { intervals }
+    }
+
+    /// Splits the set into the values below `point` and the values from `point` upwards.
+    pub fn split_at(&self, point: T) -> (Self, Self) {
+        let (mut below, mut above) = (vec![], vec![]);
+        for interval in &self.intervals {
+            let (left, right) = interval.split_at(point);
+            below.extend(left);
+            above.extend(right);
+        }
+        (Self { intervals: below }, Self { intervals: above })
+    }
+
+    /// Moves every value of the set by `offset`.
+    pub fn shift(&self, offset: T) -> Self {
+        Self {
+            intervals: self.intervals.iter().map(|i| i.shift(offset)).collect(),
+        }
+    }
+}
+
+impl<T: PrimInt> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PrimInt> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self {
+            intervals: vec![interval],
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals: Vec<Interval<T>> = iter.into_iter().collect();
+        intervals.sort_unstable();
+        Self {
+            intervals: merge(intervals),
+        }
+    }
+}
+
+impl<'a, T: PrimInt> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Merges overlapping and adjacent intervals of a list sorted by start.
+fn merge<T: PrimInt>(sorted: Vec<Interval<T>>) -> Vec<Interval<T>> {
+    let mut merged: Vec<Interval<T>> = Vec::with_capacity(sorted.len());
+    for interval in sorted {
+        match merged.last_mut() {
+            Some(last) if last.touches(&interval) => last.end = last.end.max(interval.end),
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A function that shifts the values of each source interval by an offset
+/// and maps all values outside the source intervals to themselves.
+///
+/// ```
+/// # use advent_of_code::intervals::{Interval, PiecewiseLinear};
+/// let map = PiecewiseLinear::from_iter([(Interval::new(98, 99).unwrap(), -48)]);
+/// assert_eq!(map.apply(99), 51);
+/// assert_eq!(map.apply(10), 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseLinear<T> {
+    // sorted by source start, sources are disjoint.
+    segments: Vec<(Interval<T>, T)>,
+}
+
+impl<T: PrimInt> PiecewiseLinear<T> {
+    pub fn new() -> Self {
+        Self { segments: vec![] }
+    }
+
+    /// Maps the values in `source` to `value + offset`.
+    ///
+    /// # Panics
+    /// Panics if `source` overlaps a previously inserted segment.
+    pub fn insert(&mut self, source: Interval<T>, offset: T) {
+        let idx = self
+            .segments
+            .partition_point(|(s, _)| s.start < source.start);
+        assert!(
+            !self.segments[idx.saturating_sub(1)..]
+                .iter()
+                .take(2)
+                .any(|(s, _)| s.overlaps(&source)),
+            "piecewise linear segments must not overlap"
+        );
+        self.segments.insert(idx, (source, offset));
+    }
+
+    pub fn apply(&self, value: T) -> T {
+        let idx = self.segments.partition_point(|(s, _)| s.end < value);
+        match self.segments.get(idx) {
+            Some((source, offset)) if source.contains(value) => value + *offset,
+            _ => value,
+        }
+    }
+
+    /// Maps every value of `set`.
+    pub fn apply_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let sources: IntervalSet<T> = self.segments.iter().map(|(s, _)| *s).collect();
+        let mut mapped = set.difference(&sources);
+
+        for (source, offset) in &self.segments {
+            for part in set.intersection(&IntervalSet::from(*source)).iter() {
+                mapped.insert(part.shift(*offset));
+            }
+        }
+
+        mapped
+    }
+}
+
+impl<T: PrimInt> Default for PiecewiseLinear<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PrimInt> FromIterator<(Interval<T>, T)> for PiecewiseLinear<T> {
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (source, offset) in iter {
+            map.insert(source, offset);
+        }
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An axis-aligned box of integer points with `N` dimensions.
+///
+/// ```
+/// # use advent_of_code::intervals::{Interval, IntervalBox};
+/// let ratings = IntervalBox::new([Interval::new(1u64, 4000).unwrap(); 4]);
+/// let (low, high) = ratings.split_at(0, 1351);
+/// assert_eq!(low.unwrap().volume() + high.unwrap().volume(), ratings.volume());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntervalBox<T, const N: usize> {
+    axes: [Interval<T>; N],
+}
+
+impl<T: PrimInt, const N: usize> IntervalBox<T, N> {
+    pub fn new(axes: [Interval<T>; N]) -> Self {
+        Self { axes }
+    }
+
+    pub fn axes(&self) -> &[Interval<T>; N] {
+        &self.axes
+    }
+
+    pub fn axis(&self, axis: usize) -> Interval<T> {
+        self.axes[axis]
+    }
+
+    /// Returns a copy of the box with `axis` replaced by `interval`.
+    pub fn with_axis(&self, axis: usize, interval: Interval<T>) -> Self {
+        let mut axes = self.axes;
+        axes[axis] = interval;
+        Self { axes }
+    }
+
+    /// The number of points in the box.
+    pub fn volume(&self) -> T {
+        self.axes
+            .iter()
+            .fold(T::one(), |acc, interval| acc * interval.size())
+    }
+
+    pub fn contains(&self, point: [T; N]) -> bool {
+        self.axes.iter().zip(point).all(|(i, p)| i.contains(p))
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.axes
+            .iter()
+            .zip(&other.axes)
+            .all(|(a, b)| a.overlaps(b))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut axes = self.axes;
+        for (axis, other) in axes.iter_mut().zip(&other.axes) {
+            *axis = axis.intersection(other)?;
+        }
+        Some(Self { axes })
+    }
+
+    /// Splits the box along `axis` into the points below `point` and the points from `point` upwards.
+    pub fn split_at(&self, axis: usize, point: T) -> (Option<Self>, Option<Self>) {
+        let (below, above) = self.axes[axis].split_at(point);
+        (
+            below.map(|i| self.with_axis(axis, i)),
+            above.map(|i| self.with_axis(axis, i)),
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Interval, IntervalBox, IntervalSet, PiecewiseLinear};
+
+    fn interval(start: i64, end: i64) -> Interval<i64> {
+        Interval::new(start, end).unwrap()
+    }
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals.iter().map(|&(s, e)| interval(s, e)).collect()
+    }
+
+    #[test]
+    fn interval_basics() {
+        assert_eq!(Interval::from_len(98, 2), Some(interval(98, 99)));
+        assert_eq!(Interval::from_len(98, 0), None);
+        assert!(interval(1, 3).overlaps(&interval(3, 5)));
+        assert!(!interval(1, 3).overlaps(&interval(4, 5)));
+        assert_eq!(
+            interval(1, 5).intersection(&interval(3, 8)),
+            Some(interval(3, 5))
+        );
+        assert_eq!(interval(1, 2).intersection(&interval(3, 8)), None);
+    }
+
+    #[test]
+    fn interval_split() {
+        let i = interval(1, 10);
+        assert_eq!(i.split_at(1), (None, Some(i)));
+        assert_eq!(i.split_at(11), (Some(i), None));
+        assert_eq!(i.split_at(4), (Some(interval(1, 3)), Some(interval(4, 10))));
+    }
+
+    #[test]
+    fn set_merges_overlapping_and_adjacent() {
+        let s = set(&[(5, 8), (1, 2), (3, 4), (10, 12), (11, 11)]);
+        assert_eq!(s.intervals(), &[interval(1, 8), interval(10, 12)]);
+        assert_eq!(s.size(), 11);
+        assert!(s.contains(8));
+        assert!(!s.contains(9));
+    }
+
+    #[test]
+    fn set_merges_at_type_bounds() {
+        let s: IntervalSet<u8> = [
+            Interval::new(250, 255).unwrap(),
+            Interval::new(0, 3).unwrap(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(s.intervals().len(), 2);
+        assert_eq!(s.max(), Some(255));
+    }
+
+    #[test]
+    fn set_insert() {
+        let mut s = set(&[(1, 2), (8, 9)]);
+        s.insert(interval(3, 7));
+        assert_eq!(s, set(&[(1, 9)]));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(1, 10), (20, 30)]);
+        let b = set(&[(5, 25), (28, 40)]);
+        assert_eq!(a.union(&b), set(&[(1, 40)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25), (28, 30)]));
+        assert_eq!(a.difference(&b), set(&[(1, 4), (26, 27)]));
+        assert_eq!(b.difference(&a), set(&[(11, 19), (31, 40)]));
+        assert_eq!(a.difference(&a), IntervalSet::new());
+    }
+
+    #[test]
+    fn set_difference_at_type_bounds() {
+        let a: IntervalSet<u8> = IntervalSet::from(Interval::new(0, 255).unwrap());
+        let b: IntervalSet<u8> = IntervalSet::from(Interval::new(200, 255).unwrap());
+        assert_eq!(
+            a.difference(&b).intervals(),
+            &[Interval::new(0, 199).unwrap()]
+        );
+    }
+
+    #[test]
+    fn set_split() {
+        let (below, above) = set(&[(1, 5), (8, 10)]).split_at(4);
+        assert_eq!(below, set(&[(1, 3)]));
+        assert_eq!(above, set(&[(4, 5), (8, 10)]));
+    }
+
+    #[test]
+    fn piecewise_linear_mapping() {
+        let map = PiecewiseLinear::from_iter([(interval(98, 99), -48), (interval(50, 97), 2)]);
+        assert_eq!(map.apply(79), 81);
+        assert_eq!(map.apply(98), 50);
+        assert_eq!(map.apply(100), 100);
+        assert_eq!(
+            map.apply_set(&set(&[(79, 92), (55, 67)])),
+            set(&[(57, 69), (81, 94)])
+        );
+        assert_eq!(
+            map.apply_set(&set(&[(90, 105)])),
+            set(&[(50, 51), (92, 105)])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn piecewise_linear_rejects_overlaps() {
+        PiecewiseLinear::from_iter([(interval(1, 10), 1), (interval(10, 12), 2)]);
+    }
+
+    #[test]
+    fn boxes() {
+        let a = IntervalBox::new([interval(0, 2), interval(0, 0), interval(1, 1)]);
+        let b = IntervalBox::new([interval(1, 1), interval(0, 2), interval(1, 3)]);
+        assert_eq!(a.volume(), 3);
+        assert!(a.overlaps(&b));
+        assert_eq!(
+            a.intersection(&b),
+            Some(IntervalBox::new([
+                interval(1, 1),
+                interval(0, 0),
+                interval(1, 1)
+            ]))
+        );
+        assert!(a.contains([2, 0, 1]));
+        assert!(!a.contains([2, 1, 1]));
+
+        let (low, high) = b.split_at(2, 2);
+        assert_eq!(low.unwrap().axis(2), interval(1, 1));
+        assert_eq!(high.unwrap().axis(2), interval(2, 3));
+        assert_eq!(b.split_at(2, 0), (None, Some(b)));
+    }
+}
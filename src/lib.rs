@@ -1,4 +1,15 @@
+pub mod bits;
+pub mod cycles;
 mod day;
+pub mod geometry;
+pub mod graph;
+pub mod intervals;
+pub mod linear;
+pub mod math;
+pub mod maze;
+pub mod parse;
+pub mod search;
+pub mod space;
 pub mod template;
 
 pub use day::*;
@@ -0,0 +1,143 @@
+use num::rational::Ratio;
+use num::Num;
+
+/// An exact fraction for values that fit comfortably in `i128`.
+pub type Rational = Ratio<i128>;
+
+/// An exact fraction without overflow, for systems whose intermediate values outgrow [`Rational`].
+pub use num::BigRational;
+
+/// Solves `coefficients · x = constants` by Gauss-Jordan elimination.
+///
+/// Each row of `coefficients` is one equation, with one column per unknown. There may be more
+/// equations than unknowns as long as they agree. Returns [`None`] unless there is exactly
+/// one solution. Meant for exact types like [`Rational`], as comparing floats against zero
+/// would make the result depend on rounding.
+///
+/// ```
+/// # use advent_of_code::linear::{solve, Rational};
+/// // x + 2y = 5, 3x - y = 1
+/// let r = Rational::from_integer;
+/// let solution = solve(vec![vec![r(1), r(2)], vec![r(3), r(-1)]], vec![r(5), r(1)]);
+/// assert_eq!(solution, Some(vec![r(1), r(2)]));
+/// ```
+pub fn solve<T: Clone + Num>(coefficients: Vec<Vec<T>>, constants: Vec<T>) -> Option<Vec<T>> {
+    assert_eq!(
+        coefficients.len(),
+        constants.len(),
+        "one constant per equation"
+    );
+    let unknowns = coefficients.first().map_or(0, Vec::len);
+    let mut rows: Vec<Vec<T>> = coefficients
+        .into_iter()
+        .zip(constants)
+        .map(|(mut row, constant)| {
+            assert_eq!(row.len(), unknowns, "one coefficient per unknown");
+            row.push(constant);
+            row
+        })
+        .collect();
+
+    for col in 0..unknowns {
+        let pivot = (col..rows.len()).find(|&row| !rows[row][col].is_zero())?;
+        rows.swap(col, pivot);
+
+        let divisor = rows[col][col].clone();
+        for value in rows[col].iter_mut() {
+            *value = value.clone() / divisor.clone();
+        }
+
+        let pivot_row = rows[col].clone();
+        for (index, row) in rows.iter_mut().enumerate() {
+            if index == col || row[col].is_zero() {
+                continue;
+            }
+            let factor = row[col].clone();
+            for (value, pivot) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *value = value.clone() - factor.clone() * pivot.clone();
+            }
+        }
+    }
+
+    // Surplus equations are all zero by now, unless they contradict the others.
+    match rows[unknowns..].iter().all(|row| row[unknowns].is_zero()) {
+        true => Some(
+            rows.into_iter()
+                .take(unknowns)
+                .map(|row| row[unknowns].clone())
+                .collect(),
+        ),
+        false => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{solve, BigRational, Rational};
+    use num::BigInt;
+
+    fn rows(values: &[&[i128]]) -> Vec<Vec<Rational>> {
+        values
+            .iter()
+            .map(|row| row.iter().map(|&n| Rational::from_integer(n)).collect())
+            .collect()
+    }
+
+    fn column(values: &[i128]) -> Vec<Rational> {
+        values.iter().map(|&n| Rational::from_integer(n)).collect()
+    }
+
+    #[test]
+    fn solves_square_systems() {
+        let solution = solve(
+            rows(&[&[2, 1, -1], &[-3, -1, 2], &[-2, 1, 2]]),
+            column(&[8, -11, -3]),
+        );
+        assert_eq!(solution, Some(column(&[2, 3, -1])));
+    }
+
+    #[test]
+    fn keeps_fractions_exact() {
+        let solution = solve(rows(&[&[3, 0], &[0, 7]]), column(&[1, 2])).unwrap();
+        assert_eq!(solution, vec![Rational::new(1, 3), Rational::new(2, 7)]);
+    }
+
+    #[test]
+    fn pivots_around_zeros() {
+        let solution = solve(rows(&[&[0, 1], &[1, 0]]), column(&[4, 5]));
+        assert_eq!(solution, Some(column(&[5, 4])));
+    }
+
+    #[test]
+    fn rejects_singular_systems() {
+        assert_eq!(solve(rows(&[&[1, 2], &[2, 4]]), column(&[3, 6])), None);
+        assert_eq!(solve(rows(&[&[1, 2], &[2, 4]]), column(&[3, 7])), None);
+    }
+
+    #[test]
+    fn checks_surplus_equations() {
+        let consistent = solve(rows(&[&[1, 1], &[1, -1], &[2, 1]]), column(&[3, 1, 5]));
+        assert_eq!(consistent, Some(column(&[2, 1])));
+        let contradicting = solve(rows(&[&[1, 1], &[1, -1], &[2, 1]]), column(&[3, 1, 6]));
+        assert_eq!(contradicting, None);
+    }
+
+    #[test]
+    fn solves_without_overflow() {
+        let big = |n: i128| BigRational::from_integer(BigInt::from(n));
+        let huge = 400_000_000_000_000_000_000_000_000_000_000i128;
+        let solution = solve(
+            vec![
+                vec![big(huge), big(huge - 1)],
+                vec![big(huge + 1), big(huge)],
+            ],
+            vec![big(1), big(2)],
+        );
+        assert_eq!(
+            solution,
+            Some(vec![big(huge - 2 * (huge - 1)), big(2 * huge - huge - 1)])
+        );
+    }
+}
@@ -1,9 +1,10 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, status};
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
 
+    use advent_of_code::template::config::Overrides;
     use advent_of_code::Day;
 
     pub enum AppArguments {
@@ -12,6 +13,7 @@ mod args {
         },
         Read {
             day: Day,
+            part: Option<u8>,
         },
         Scaffold {
             day: Day,
@@ -21,26 +23,39 @@ mod args {
             release: bool,
             time: bool,
             submit: Option<u8>,
+            debug: Option<String>,
         },
         All {
             release: bool,
             time: bool,
+            cached: bool,
+        },
+        Status {
+            release: bool,
         },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // overrides are read by `config::get()` directly.
+        // consume them before the positional day so they are validated and can precede it.
+        Overrides::parse(&mut args)?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                cached: args.contains("--cached"),
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                part: args.opt_value_from_str("--part")?,
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
@@ -50,6 +65,10 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                debug: args.opt_value_from_str("--debug")?,
+            },
+            Some("status") => AppArguments::Status {
+                release: args.contains("--release"),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -77,16 +96,22 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
+            AppArguments::All {
+                release,
+                time,
+                cached,
+            } => all::handle(release, time, cached),
             AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Read { day, part } => read::handle(day, part),
             AppArguments::Scaffold { day } => scaffold::handle(day),
             AppArguments::Solve {
                 day,
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                debug,
+            } => solve::handle(day, release, time, submit, debug),
+            AppArguments::Status { release } => status::handle(release),
         },
     };
 }
@@ -0,0 +1,257 @@
+use crate::intervals::Interval;
+use num::{PrimInt, Signed, Unsigned};
+
+/// Greatest common divisor of two non-negative numbers, `gcd(0, 0)` is `0`.
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while !b.is_zero() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple of two non-negative numbers, `0` if either of them is `0`.
+pub fn lcm<T: PrimInt>(a: T, b: T) -> T {
+    match a.is_zero() || b.is_zero() {
+        true => T::zero(),
+        false => a / gcd(a, b) * b,
+    }
+}
+
+/// Least common multiple of all numbers, [`None`] if there are none.
+///
+/// ```
+/// # use advent_of_code::math::lcm_all;
+/// assert_eq!(lcm_all([4u64, 6, 10]), Some(60));
+/// assert_eq!(lcm_all(Vec::<u64>::new()), None);
+/// ```
+pub fn lcm_all<T: PrimInt>(numbers: impl IntoIterator<Item = T>) -> Option<T> {
+    numbers.into_iter().reduce(lcm)
+}
+
+/// Extended Euclidean algorithm
+///
+/// Returns `(g, x, y)` with `g = gcd(a, b) ≥ 0` and `a * x + b * y = g`.
+/// https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while !r.is_zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    match old_r.is_negative() {
+        true => (-old_r, -old_x, -old_y),
+        false => (old_r, old_x, old_y),
+    }
+}
+
+/// Inverse of `a` modulo `m`, [`None`] if `a` and `m` are not coprime or `m` is zero.
+///
+/// ```
+/// # use advent_of_code::math::mod_inv;
+/// assert_eq!(mod_inv(3, 11), Some(4));
+/// assert_eq!(mod_inv(4, 8), None);
+/// ```
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    if m == 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(i128::from(a), i128::from(m));
+    match g == 1 {
+        true => Some(x.rem_euclid(i128::from(m)) as i64),
+        false => None,
+    }
+}
+
+/// `base` raised to `exp` modulo `modulus`, by squaring.
+///
+/// Panics if `modulus` is zero.
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    assert!(modulus > 0, "mod_pow needs a non-zero modulus");
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut exp = exp;
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    result as u64
+}
+
+/// Chinese Remainder Theorem
+///
+/// Combines congruences `x ≡ rᵢ (mod mᵢ)` into a single `x ≡ r (mod m)`, returned as `(r, m)`.
+/// The moduli need not be coprime, [`None`] if the congruences contradict each other or the
+/// combined modulus does not fit in an `i64`.
+///
+/// ```
+/// # use advent_of_code::math::crt;
+/// assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt([(1, 4), (2, 6)]), None);
+/// ```
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (r, m) = congruences
+        .into_iter()
+        .try_fold((0i128, 1i128), |(r1, m1), (r2, m2)| {
+            let (r2, m2) = (i128::from(r2).rem_euclid(i128::from(m2)), i128::from(m2));
+            let (g, x, _) = extended_gcd(m1, m2);
+            if (r2 - r1) % g != 0 {
+                return None;
+            }
+            let m = m1 / g * m2;
+            // Keeping `m` within an `i64` also keeps the products below within an `i128`.
+            i64::try_from(m).ok()?;
+            // m1 * x ≡ g (mod m2), so stepping (r2 - r1) / g times that from r1 lands on r2.
+            let k = ((r2 - r1) / g % (m2 / g)) * x % (m2 / g);
+            Some(((r1 + m1 * k).rem_euclid(m), m))
+        })?;
+    Some((i64::try_from(r).ok()?, i64::try_from(m).ok()?))
+}
+
+/// Integer square root, the largest `r` with `r² ≤ n`.
+///
+/// Newton's method from an initial guess above the root, so it never relies on `f64` rounding.
+///
+/// ```
+/// # use advent_of_code::math::isqrt;
+/// assert_eq!(isqrt(24u64), 4);
+/// assert_eq!(isqrt(25u64), 5);
+/// assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+/// ```
+pub fn isqrt<T: PrimInt + Unsigned>(n: T) -> T {
+    if n < T::from(2).unwrap() {
+        return n;
+    }
+    let bits = T::zero().count_zeros() - n.leading_zeros();
+    let mut x = T::one() << bits.div_ceil(2) as usize;
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// The integers `x` satisfying `a·x² + b·x + c < 0`, for an upward opening parabola (`a > 0`).
+///
+/// Returns [`None`] if there are none. Since the values are integers, `≤ 0` is solved as `< 1`
+/// by passing `c - 1`. The roots `(-b ± √(b² - 4ac)) / 2a` are bracketed with [`isqrt`]
+/// and then narrowed by evaluating the polynomial exactly.
+///
+/// ```
+/// # use advent_of_code::math::quadratic_below;
+/// // x² - 7x + 9 < 0
+/// let solutions = quadratic_below(1, -7, 9).unwrap();
+/// assert_eq!((solutions.start(), solutions.end()), (2, 5));
+/// ```
+pub fn quadratic_below(a: i64, b: i64, c: i64) -> Option<Interval<i64>> {
+    assert!(a > 0, "the parabola must open upwards");
+    let (a, b, c) = (i128::from(a), i128::from(b), i128::from(c));
+    let f = |x: i128| (a * x + b) * x + c;
+
+    let discriminant = b * b - 4 * a * c;
+    if discriminant <= 0 {
+        return None;
+    }
+    let root = isqrt(discriminant as u128) as i128;
+
+    // With root ≤ √D < root + 1 these bracket the real roots, so both start outside the solutions.
+    let mut low = (-b - root - 1).div_euclid(2 * a);
+    let mut high = -(b - root - 1).div_euclid(2 * a);
+    while low <= high && f(low) >= 0 {
+        low += 1;
+    }
+    while high >= low && f(high) >= 0 {
+        high -= 1;
+    }
+
+    Interval::new(low as i64, high as i64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crt, extended_gcd, gcd, isqrt, lcm, mod_inv, mod_pow, quadratic_below};
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(0u64, 7), 7);
+        assert_eq!(gcd(0u64, 0), 0);
+        assert_eq!(lcm(4usize, 6), 12);
+        assert_eq!(lcm(0usize, 6), 0);
+    }
+
+    #[test]
+    fn computes_bezout_coefficients() {
+        for (a, b) in [(240i64, 46), (46, 240), (-12, 18), (7, 0), (0, -5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a.abs(), b.abs()));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, 1_000_000_007), 254368884);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(10, 17).map(|x| x * 10 % 17), Some(1));
+        assert_eq!(mod_inv(3, 0), None);
+    }
+
+    #[test]
+    fn combines_congruences() {
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([(0, 6), (0, 4), (3, 9)]), Some((12, 36)));
+        assert_eq!(crt([(0, 6), (0, 4), (4, 9)]), None);
+        assert_eq!(crt([(2, 6), (5, 9), (2, 4)]), Some((14, 36)));
+        assert_eq!(crt([(1, i64::MAX), (0, 2)]), None);
+    }
+
+    #[test]
+    fn computes_exact_square_roots() {
+        for n in 0u64..1000 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n);
+        }
+        assert_eq!(isqrt(u64::MAX), u64::from(u32::MAX));
+        let large = 999_999_999_999_999_999u128;
+        assert_eq!(isqrt(large * large), large);
+        assert_eq!(isqrt(large * large - 1), large - 1);
+    }
+
+    #[test]
+    fn solves_quadratic_inequalities() {
+        let range = |a, b, c| quadratic_below(a, b, c).map(|i| (i.start(), i.end()));
+        assert_eq!(range(1, -30, 200), Some((11, 19)));
+        assert_eq!(range(1, 0, -1), Some((0, 0)));
+        assert_eq!(range(1, 0, 0), None);
+        assert_eq!(range(1, -2, 1), None);
+        assert_eq!(range(4, 0, -1), Some((0, 0)));
+        assert_eq!(range(4, -6, 2), None);
+        assert_eq!(range(2, 1, -10), Some((-2, 1)));
+        let time = 40_828_492;
+        let (low, high) = range(1, -time, 233_101_111_101_487).unwrap();
+        assert!(low * (time - low) > 233_101_111_101_487);
+        assert!((low - 1) * (time - low + 1) <= 233_101_111_101_487);
+        assert_eq!(low + high, time);
+    }
+}
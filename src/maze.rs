@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+
+/// A cell of a maze as `(row, col)`.
+pub type Cell = (usize, usize);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The cell one step away, unless that would leave the first row or column.
+    pub fn step(self, (row, col): Cell) -> Option<Cell> {
+        match self {
+            Direction::North => row.checked_sub(1).map(|row| (row, col)),
+            Direction::East => Some((row, col + 1)),
+            Direction::South => Some((row + 1, col)),
+            Direction::West => col.checked_sub(1).map(|col| (row, col)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Tile {
+    Wall,
+    Open,
+    /// Open, but can only be left in the given direction, like a slope or a conveyor.
+    OneWay(Direction),
+}
+
+/// A grid of tiles that can be collapsed into a graph of junctions joined by corridors.
+#[derive(Debug, Clone)]
+pub struct Maze {
+    tiles: Vec<Vec<Tile>>,
+}
+
+/// A corridor between two junctions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Corridor {
+    /// The index of the junction the corridor leads to.
+    pub to: usize,
+    /// Every cell walked through, including both junctions.
+    pub path: Vec<Cell>,
+}
+
+impl Corridor {
+    /// The number of steps along the corridor.
+    pub fn length(&self) -> usize {
+        self.path.len() - 1
+    }
+}
+
+/// A maze reduced to its junctions, each with the corridors that leave it.
+#[derive(Debug, Clone)]
+pub struct Junctions {
+    pub cells: Vec<Cell>,
+    pub corridors: Vec<Vec<Corridor>>,
+}
+
+impl Junctions {
+    /// The index of the junction at `cell`.
+    pub fn index(&self, cell: Cell) -> Option<usize> {
+        self.cells.iter().position(|&c| c == cell)
+    }
+
+    /// The cells of a route given as `(junction, corridor)` pairs, each corridor indexing
+    /// into `corridors[junction]`.
+    pub fn cells(&self, route: &[(usize, usize)]) -> Vec<Cell> {
+        let mut cells = vec![];
+        for &(junction, corridor) in route {
+            let path = &self.corridors[junction][corridor].path;
+            let skip = match cells.last() == path.first() {
+                true => 1,
+                false => 0,
+            };
+            cells.extend(&path[skip..]);
+        }
+        cells
+    }
+}
+
+impl Maze {
+    pub fn new(tiles: Vec<Vec<Tile>>) -> Self {
+        Maze { tiles }
+    }
+
+    /// A maze with a row per line, classifying each character with `tile`.
+    pub fn parse(input: &str, tile: impl Fn(char) -> Tile) -> Self {
+        Maze::new(
+            input
+                .lines()
+                .map(|line| line.chars().map(&tile).collect())
+                .collect(),
+        )
+    }
+
+    pub fn height(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.tiles.first().map_or(0, Vec::len)
+    }
+
+    /// The tile at `cell`, with everything outside the grid a wall.
+    pub fn tile(&self, (row, col): Cell) -> Tile {
+        self.tiles
+            .get(row)
+            .and_then(|line| line.get(col))
+            .copied()
+            .unwrap_or(Tile::Wall)
+    }
+
+    /// Every cell that is not a wall, row by row.
+    pub fn open_cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        self.tiles.iter().enumerate().flat_map(|(row, line)| {
+            line.iter()
+                .enumerate()
+                .filter(|(_, tile)| **tile != Tile::Wall)
+                .map(move |(col, _)| (row, col))
+        })
+    }
+
+    /// The open cells one step from `cell`. With `one_way`, a one-way tile can only be left
+    /// in its own direction.
+    pub fn neighbours(&self, cell: Cell, one_way: bool) -> Vec<Cell> {
+        let directions = match (one_way, self.tile(cell)) {
+            (_, Tile::Wall) => vec![],
+            (true, Tile::OneWay(direction)) => vec![direction],
+            _ => Direction::ALL.to_vec(),
+        };
+        directions
+            .into_iter()
+            .filter_map(|direction| direction.step(cell))
+            .filter(|&next| self.tile(next) != Tile::Wall)
+            .collect()
+    }
+
+    /// Open cells where more than two paths meet.
+    pub fn forks(&self) -> impl Iterator<Item = Cell> + '_ {
+        self.open_cells()
+            .filter(|&cell| self.neighbours(cell, false).len() > 2)
+    }
+
+    /// Collapses the maze into its forks plus the given `waypoints` (typically the start
+    /// and the end), joined by the corridors between them.
+    ///
+    /// Corridors that dead-end, loop back to where they started, or (with `one_way`) run
+    /// against a one-way tile are left out.
+    ///
+    /// ```
+    /// # use advent_of_code::maze::{Maze, Tile};
+    /// let maze = Maze::parse("#.###\n#...#\n#.#.#\n#...#\n###.#", |c| match c {
+    ///     '#' => Tile::Wall,
+    ///     _ => Tile::Open,
+    /// });
+    /// let junctions = maze.junctions([(0, 1), (4, 3)], false);
+    /// assert_eq!(junctions.cells, vec![(0, 1), (4, 3), (1, 1), (3, 3)]);
+    /// let lengths: Vec<_> = junctions.corridors[2].iter().map(|c| c.length()).collect();
+    /// assert_eq!(lengths, vec![1, 4, 4]);
+    /// ```
+    pub fn junctions(&self, waypoints: impl IntoIterator<Item = Cell>, one_way: bool) -> Junctions {
+        let mut cells: Vec<Cell> = waypoints.into_iter().collect();
+        for fork in self.forks() {
+            if !cells.contains(&fork) {
+                cells.push(fork);
+            }
+        }
+        let index: HashMap<Cell, usize> = cells.iter().enumerate().map(|(i, &c)| (c, i)).collect();
+
+        let corridors = cells
+            .iter()
+            .enumerate()
+            .map(|(from, &cell)| {
+                self.neighbours(cell, one_way)
+                    .into_iter()
+                    .filter_map(|first| self.follow(vec![cell, first], &index, one_way))
+                    .filter(|corridor| corridor.to != from)
+                    .collect()
+            })
+            .collect();
+        Junctions { cells, corridors }
+    }
+
+    /// Walks a corridor that starts with `path` until it reaches a junction.
+    fn follow(
+        &self,
+        mut path: Vec<Cell>,
+        junctions: &HashMap<Cell, usize>,
+        one_way: bool,
+    ) -> Option<Corridor> {
+        loop {
+            let current = *path.last()?;
+            if let Some(&to) = junctions.get(&current) {
+                return Some(Corridor { to, path });
+            }
+            let previous = path[path.len() - 2];
+            let next = self
+                .neighbours(current, one_way)
+                .into_iter()
+                .find(|&next| next != previous)?;
+            path.push(next);
+        }
+    }
+
+    /// The maze drawn with `#` for walls, `.` for open cells, arrows for one-way tiles
+    /// and `O` for the cells of `route`.
+    pub fn draw(&self, route: &[Cell]) -> String {
+        let mut rows: Vec<Vec<char>> = self
+            .tiles
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|tile| match tile {
+                        Tile::Wall => '#',
+                        Tile::Open => '.',
+                        Tile::OneWay(Direction::North) => '^',
+                        Tile::OneWay(Direction::East) => '>',
+                        Tile::OneWay(Direction::South) => 'v',
+                        Tile::OneWay(Direction::West) => '<',
+                    })
+                    .collect()
+            })
+            .collect();
+        for &(row, col) in route {
+            rows[row][col] = 'O';
+        }
+        rows.into_iter()
+            .map(|row| row.into_iter().collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Maze, Tile};
+
+    fn maze(input: &str) -> Maze {
+        Maze::parse(input, |c| match c {
+            '#' => Tile::Wall,
+            '>' => Tile::OneWay(Direction::East),
+            'v' => Tile::OneWay(Direction::South),
+            _ => Tile::Open,
+        })
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let maze = maze("#.#\n.>.\n#v#");
+        assert_eq!(maze.neighbours((1, 1), false).len(), 4);
+        assert_eq!(maze.neighbours((1, 1), true), vec![(1, 2)]);
+        assert_eq!(maze.neighbours((0, 1), true), vec![(1, 1)]);
+        assert_eq!(maze.forks().collect::<Vec<_>>(), vec![(1, 1)]);
+        assert_eq!((maze.width(), maze.height()), (3, 3));
+    }
+
+    #[test]
+    fn keeps_corridor_paths() {
+        let maze = maze("#.####\n#....#\n####.#\n####.#");
+        let junctions = maze.junctions([(0, 1), (3, 4)], false);
+        assert_eq!(junctions.corridors[0].len(), 1);
+        let corridor = &junctions.corridors[0][0];
+        assert_eq!(corridor.to, 1);
+        assert_eq!(corridor.length(), 6);
+        assert_eq!(
+            corridor.path,
+            vec![(0, 1), (1, 1), (1, 2), (1, 3), (1, 4), (2, 4), (3, 4)]
+        );
+    }
+
+    #[test]
+    fn respects_one_way_tiles() {
+        let maze = maze("#.####\n#.>..#\n####.#\n####.#");
+        assert_eq!(maze.junctions([(0, 1), (3, 4)], true).corridors[1], vec![]);
+        assert_eq!(
+            maze.junctions([(0, 1), (3, 4)], false).corridors[1].len(),
+            1
+        );
+    }
+
+    #[test]
+    fn drops_dead_ends_and_self_loops() {
+        let branches = maze("#.#####\n#.....#\n#.#.#.#\n#...#.#\n#.#####");
+        let junctions = branches.junctions([(0, 1), (4, 1)], false);
+        // Back to (1, 1) and round to (3, 1), but not into the dead end on the right.
+        let fork = junctions.index((1, 3)).unwrap();
+        assert_eq!(junctions.corridors[fork].len(), 2);
+        assert!(junctions
+            .corridors
+            .iter()
+            .flatten()
+            .all(|corridor| !corridor.path.contains(&(1, 5))));
+
+        let ring = maze("#.###\n#...#\n#.#.#\n#...#\n#####");
+        let junctions = ring.junctions([(0, 1)], false);
+        assert_eq!(junctions.corridors[1].len(), 1);
+    }
+
+    #[test]
+    fn draws_routes() {
+        let maze = maze("#.###\n#.>.#\n###.#");
+        let junctions = maze.junctions([(0, 1), (2, 3)], true);
+        let cells = junctions.cells(&[(0, 0)]);
+        assert_eq!(maze.draw(&cells), "#O###\n#OOO#\n###O#\n");
+        assert_eq!(maze.draw(&[]), "#.###\n#.>.#\n###.#\n");
+    }
+}
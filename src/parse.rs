@@ -0,0 +1,214 @@
+use nom::bytes::complete::tag;
+use nom::character::complete::{
+    char, digit1, line_ending, multispace0, none_of, one_of, space0, space1,
+};
+use nom::combinator::{all_consuming, map_res, opt, recognize};
+use nom::error::Error;
+use nom::multi::{many1, separated_list1};
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
+use nom::{Finish, IResult, Parser};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Where and why a parser stopped before the end of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The rest of the offending line.
+    pub near: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unexpected input at line {}, column {}: \"{}\"",
+            self.line, self.column, self.near
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl ParseError {
+    fn at(input: &str, remaining: &str) -> Self {
+        let consumed = &input[..input.len() - remaining.len()];
+        Self {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed.len() - consumed.rfind('\n').map_or(0, |i| i + 1) + 1,
+            near: remaining.lines().next().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+/// Runs `parser` on the whole input, which may only be followed by trailing whitespace.
+///
+/// Unlike calling the parser directly, a partial parse is an error rather than silently
+/// dropping whatever could not be parsed.
+///
+/// ```
+/// # use advent_of_code::parse::{numbers, parse_all};
+/// assert_eq!(parse_all(numbers::<u32>, "1 2 3\n"), Ok(vec![1, 2, 3]));
+/// assert_eq!(parse_all(numbers::<u32>, "1 2 x").unwrap_err().column, 5);
+/// ```
+pub fn parse_all<'a, O, F>(parser: F, input: &'a str) -> Result<O, ParseError>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    all_consuming(terminated(parser, multispace0))(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|e| ParseError::at(input, e.input))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A decimal integer with an optional sign.
+pub fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Integers separated by spaces or tabs, like `7  15   -30`.
+pub fn numbers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(space1, number)(input)
+}
+
+/// Integers separated by `separator`, with optional spaces around it, like `19, 13, 30`.
+pub fn separated_numbers<'a, T: FromStr>(
+    separator: char,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(delimited(space0, char(separator), space0), number)
+}
+
+/// One or more lines, each parsed by `parser`.
+pub fn lines<'a, O, F>(parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(line_ending, parser)
+}
+
+/// One or more blocks separated by a blank line, each parsed by `parser`.
+pub fn blocks<'a, O, F>(parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(pair(line_ending, line_ending), parser)
+}
+
+/// A `key: value` pair, with any spaces around the colon.
+pub fn key_value<'a, K, V, FK, FV>(
+    key: FK,
+    value: FV,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)>
+where
+    FK: Parser<&'a str, K, Error<&'a str>>,
+    FV: Parser<&'a str, V, Error<&'a str>>,
+{
+    separated_pair(key, tuple((space0, char(':'), space0)), value)
+}
+
+/// The value after a fixed `label:` header, like the numbers in `Time:  7  15  30`.
+pub fn labelled<'a, O, F>(label: &'a str, value: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    preceded(tuple((tag(label), char(':'), space0)), value)
+}
+
+/// Rows of cells, each cell parsed by `cell`.
+pub fn grid<'a, O, F>(cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<O>>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    lines(many1(cell))
+}
+
+/// Rows of characters, up to the end of the input or the first blank line.
+pub fn char_grid(input: &str) -> IResult<&str, Vec<Vec<char>>> {
+    grid(none_of("\r\n"))(input)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        blocks, char_grid, grid, key_value, labelled, lines, number, numbers, parse_all,
+        separated_numbers, ParseError,
+    };
+    use nom::character::complete::{alpha1, one_of};
+
+    #[test]
+    fn parses_signed_numbers() {
+        assert_eq!(number::<i64>("-12 rest"), Ok((" rest", -12)));
+        assert_eq!(number::<i64>("+7"), Ok(("", 7)));
+        assert!(number::<u8>("-1").is_err());
+        assert!(number::<u8>("256").is_err());
+    }
+
+    #[test]
+    fn parses_number_lists() {
+        assert_eq!(numbers::<i32>("1  -2\t3\n4"), Ok(("\n4", vec![1, -2, 3])));
+        assert_eq!(
+            separated_numbers::<i128>(',')("19, 13,30 @ -2"),
+            Ok((" @ -2", vec![19, 13, 30]))
+        );
+    }
+
+    #[test]
+    fn parses_lines_and_blocks() {
+        let input = "1 2\n3\n\n4 5\r\n6";
+        assert_eq!(
+            parse_all(blocks(lines(numbers::<u8>)), input),
+            Ok(vec![vec![vec![1, 2], vec![3]], vec![vec![4, 5], vec![6]]])
+        );
+    }
+
+    #[test]
+    fn parses_headers() {
+        assert_eq!(
+            parse_all(labelled("Time", numbers::<u32>), "Time:      7  15   30"),
+            Ok(vec![7, 15, 30])
+        );
+        assert_eq!(
+            parse_all(lines(key_value(alpha1, numbers::<u32>)), "a: 1 2\nbc :3"),
+            Ok(vec![("a", vec![1, 2]), ("bc", vec![3])])
+        );
+    }
+
+    #[test]
+    fn parses_grids() {
+        assert_eq!(
+            parse_all(char_grid, "#.\n.#\n"),
+            Ok(vec![vec!['#', '.'], vec!['.', '#']])
+        );
+        assert_eq!(
+            parse_all(blocks(char_grid), "#\n\n.").map(|b| b.len()),
+            Ok(2)
+        );
+        assert!(parse_all(grid(one_of(".#")), "#.\n.x").is_err());
+    }
+
+    #[test]
+    fn rejects_partial_parses() {
+        assert_eq!(
+            parse_all(lines(numbers::<u32>), "1 2\n3 4\nfive 6\n"),
+            Err(ParseError {
+                line: 3,
+                column: 1,
+                near: "five 6".into()
+            })
+        );
+        assert_eq!(
+            parse_all(labelled("Time", numbers::<u32>), "Time: 1 2 x"),
+            Err(ParseError {
+                line: 1,
+                column: 11,
+                near: "x".into()
+            })
+        );
+        assert_eq!(parse_all(numbers::<u32>, "1 2 \n\n"), Ok(vec![1, 2]));
+    }
+}
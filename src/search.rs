@@ -0,0 +1,445 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// A state space explored by unweighted searches.
+///
+/// Implemented for closures `Fn(&S) -> impl IntoIterator<Item = S>`,
+/// so a search can be run over an ad hoc neighbour function as well as a dedicated type.
+pub trait Neighbours<S> {
+    fn neighbours(&self, state: &S) -> impl IntoIterator<Item = S>;
+}
+
+impl<S, F, I> Neighbours<S> for F
+where
+    F: Fn(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    fn neighbours(&self, state: &S) -> impl IntoIterator<Item = S> {
+        self(state)
+    }
+}
+
+/// A state space with a cost for moving to each neighbour.
+///
+/// Implemented for closures `Fn(&S) -> impl IntoIterator<Item = (S, usize)>`.
+pub trait WeightedNeighbours<S> {
+    fn weighted_neighbours(&self, state: &S) -> impl IntoIterator<Item = (S, usize)>;
+}
+
+impl<S, F, I> WeightedNeighbours<S> for F
+where
+    F: Fn(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    fn weighted_neighbours(&self, state: &S) -> impl IntoIterator<Item = (S, usize)> {
+        self(state)
+    }
+}
+
+/// The outcome of a search: the cost of every reached state and how it was reached.
+#[derive(Debug, Clone)]
+pub struct Explored<S> {
+    costs: HashMap<S, usize>,
+    parents: HashMap<S, S>,
+    goal: Option<S>,
+    expanded: usize,
+}
+
+impl<S: Hash + Eq + Clone> Explored<S> {
+    fn new(starts: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            costs: starts.into_iter().map(|start| (start, 0)).collect(),
+            parents: HashMap::new(),
+            goal: None,
+            expanded: 0,
+        }
+    }
+
+    /// Records `state` as reached from `parent` at `cost`, unless it was already reached cheaper.
+    fn relax(&mut self, state: &S, parent: &S, cost: usize) -> bool {
+        match self.costs.get(state) {
+            Some(&known) if known <= cost => false,
+            _ => {
+                self.costs.insert(state.clone(), cost);
+                self.parents.insert(state.clone(), parent.clone());
+                true
+            }
+        }
+    }
+
+    /// The cost (or distance) of the cheapest known way to `state`.
+    pub fn cost(&self, state: &S) -> Option<usize> {
+        self.costs.get(state).copied()
+    }
+
+    /// All reached states with their cost.
+    pub fn costs(&self) -> &HashMap<S, usize> {
+        &self.costs
+    }
+
+    /// The states along the cheapest known way from a start to `state`, both included.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        self.costs.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The first state satisfying the goal of the search, if it was found.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    pub fn goal_cost(&self) -> Option<usize> {
+        self.cost(self.goal.as_ref()?)
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.path(self.goal.as_ref()?)
+    }
+
+    /// The number of distinct states reached.
+    pub fn visited(&self) -> usize {
+        self.costs.len()
+    }
+
+    /// The number of times a state was taken from the queue and had its neighbours generated.
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Breadth-first search from every start at once, exploring all reachable states.
+///
+/// ```
+/// # use advent_of_code::search::bfs;
+/// let explored = bfs([0], &|n: &u32| [n + 3, n + 5].into_iter().filter(|n| *n <= 20));
+/// assert_eq!(explored.cost(&11), Some(3));
+/// assert_eq!(explored.path(&11), Some(vec![0, 3, 6, 11]));
+/// ```
+pub fn bfs<S, N>(starts: impl IntoIterator<Item = S>, graph: &N) -> Explored<S>
+where
+    S: Hash + Eq + Clone,
+    N: Neighbours<S>,
+{
+    breadth_first(starts, graph, usize::MAX, |_| false)
+}
+
+/// Breadth-first search that does not go further than `max_distance` steps from the starts.
+pub fn bfs_within<S, N>(
+    starts: impl IntoIterator<Item = S>,
+    graph: &N,
+    max_distance: usize,
+) -> Explored<S>
+where
+    S: Hash + Eq + Clone,
+    N: Neighbours<S>,
+{
+    breadth_first(starts, graph, max_distance, |_| false)
+}
+
+/// Breadth-first search that stops at the first state satisfying `goal`.
+pub fn bfs_until<S, N, G>(starts: impl IntoIterator<Item = S>, graph: &N, goal: G) -> Explored<S>
+where
+    S: Hash + Eq + Clone,
+    N: Neighbours<S>,
+    G: Fn(&S) -> bool,
+{
+    breadth_first(starts, graph, usize::MAX, goal)
+}
+
+fn breadth_first<S, N, G>(
+    starts: impl IntoIterator<Item = S>,
+    graph: &N,
+    max_distance: usize,
+    goal: G,
+) -> Explored<S>
+where
+    S: Hash + Eq + Clone,
+    N: Neighbours<S>,
+    G: Fn(&S) -> bool,
+{
+    let mut explored = Explored::new(starts);
+    let mut queue: VecDeque<(S, usize)> = explored.costs.keys().map(|s| (s.clone(), 0)).collect();
+
+    while let Some((state, distance)) = queue.pop_front() {
+        explored.expanded += 1;
+        if goal(&state) {
+            explored.goal = Some(state);
+            break;
+        }
+        if distance == max_distance {
+            continue;
+        }
+        for next in graph.neighbours(&state) {
+            if !explored.costs.contains_key(&next) {
+                explored.relax(&next, &state, distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    explored
+}
+
+/// All states reachable from the starts, in no particular order.
+///
+/// Cheaper than [`bfs`] when neither distances nor paths are needed.
+pub fn flood_fill<S, N>(starts: impl IntoIterator<Item = S>, graph: &N) -> HashSet<S>
+where
+    S: Hash + Eq + Clone,
+    N: Neighbours<S>,
+{
+    let mut stack: Vec<S> = starts.into_iter().collect();
+    let mut filled: HashSet<S> = stack.iter().cloned().collect();
+
+    while let Some(state) = stack.pop() {
+        for next in graph.neighbours(&state) {
+            if filled.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+
+    filled
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Dijkstra's algorithm, stopping at the first state satisfying `goal`.
+///
+/// States are queued in buckets indexed by their cost (Dial's algorithm),
+/// which beats a binary heap for the small integer costs of grid puzzles.
+///
+/// ```
+/// # use advent_of_code::search::dijkstra;
+/// let explored = dijkstra([1u32], &|n: &u32| [(n * 2, 1), (n + 1, 3)], |n| *n == 10);
+/// assert_eq!(explored.goal_cost(), Some(6));
+/// assert_eq!(explored.goal_path(), Some(vec![1, 2, 4, 5, 10]));
+/// ```
+pub fn dijkstra<S, N, G>(starts: impl IntoIterator<Item = S>, graph: &N, goal: G) -> Explored<S>
+where
+    S: Hash + Eq + Clone,
+    N: WeightedNeighbours<S>,
+    G: Fn(&S) -> bool,
+{
+    let mut explored = Explored::new(starts);
+    let mut buckets: Vec<Vec<S>> = vec![explored.costs.keys().cloned().collect()];
+    let mut cost = 0;
+
+    while cost < buckets.len() {
+        while let Some(state) = buckets[cost].pop() {
+            if explored.costs[&state] < cost {
+                continue;
+            }
+            explored.expanded += 1;
+            if goal(&state) {
+                explored.goal = Some(state);
+                return explored;
+            }
+            for (next, weight) in graph.weighted_neighbours(&state) {
+                let next_cost = cost + weight;
+                if explored.relax(&next, &state, next_cost) {
+                    if buckets.len() <= next_cost {
+                        buckets.resize_with(next_cost + 1, Vec::new);
+                    }
+                    buckets[next_cost].push(next);
+                }
+            }
+        }
+        cost += 1;
+    }
+
+    explored
+}
+
+/// A* search, stopping at the first state satisfying `goal`.
+///
+/// The result is only guaranteed to be the cheapest if `heuristic` never overestimates
+/// the remaining cost.
+pub fn astar<S, N, H, G>(
+    starts: impl IntoIterator<Item = S>,
+    graph: &N,
+    heuristic: H,
+    goal: G,
+) -> Explored<S>
+where
+    S: Hash + Eq + Clone,
+    N: WeightedNeighbours<S>,
+    H: Fn(&S) -> usize,
+    G: Fn(&S) -> bool,
+{
+    let mut explored = Explored::new(starts);
+    // The heap orders indices into `states`, so the states themselves need not be `Ord`.
+    let mut states: Vec<S> = explored.costs.keys().cloned().collect();
+    let mut heap: BinaryHeap<Reverse<(usize, usize, usize)>> = states
+        .iter()
+        .enumerate()
+        .map(|(index, state)| Reverse((heuristic(state), 0, index)))
+        .collect();
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let state = states[index].clone();
+        if explored.costs[&state] < cost {
+            continue;
+        }
+        explored.expanded += 1;
+        if goal(&state) {
+            explored.goal = Some(state);
+            break;
+        }
+        for (next, weight) in graph.weighted_neighbours(&state) {
+            let next_cost = cost + weight;
+            if explored.relax(&next, &state, next_cost) {
+                heap.push(Reverse((
+                    next_cost + heuristic(&next),
+                    next_cost,
+                    states.len(),
+                )));
+                states.push(next);
+            }
+        }
+    }
+
+    explored
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, bfs_until, bfs_within, dijkstra, flood_fill, Neighbours};
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    struct Maze(Vec<Vec<char>>);
+
+    impl Maze {
+        fn new() -> Self {
+            Maze(MAZE.lines().map(|line| line.chars().collect()).collect())
+        }
+
+        fn find(&self, c: char) -> (usize, usize) {
+            (0..self.0.len())
+                .flat_map(|row| (0..self.0[0].len()).map(move |col| (row, col)))
+                .find(|&(row, col)| self.0[row][col] == c)
+                .unwrap()
+        }
+    }
+
+    impl Neighbours<(usize, usize)> for Maze {
+        fn neighbours(
+            &self,
+            &(row, col): &(usize, usize),
+        ) -> impl IntoIterator<Item = (usize, usize)> {
+            [
+                (row.wrapping_sub(1), col),
+                (row + 1, col),
+                (row, col.wrapping_sub(1)),
+                (row, col + 1),
+            ]
+            .into_iter()
+            .filter(|&(row, col)| row < self.0.len() && col < self.0[0].len())
+            .filter(|&(row, col)| self.0[row][col] != '#')
+        }
+    }
+
+    #[test]
+    fn finds_shortest_paths() {
+        let maze = Maze::new();
+        let (start, end) = (maze.find('S'), maze.find('E'));
+        let explored = bfs([start], &maze);
+        assert_eq!(explored.cost(&end), Some(15));
+
+        let path = explored.path(&end).unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+    }
+
+    #[test]
+    fn searches_from_multiple_sources() {
+        let maze = Maze::new();
+        let explored = bfs([maze.find('S'), maze.find('E')], &maze);
+        assert_eq!(explored.cost(&(0, 7)), Some(4));
+        assert_eq!(explored.cost(&(4, 0)), Some(4));
+        assert_eq!(explored.path(&(0, 0)), Some(vec![(0, 0)]));
+    }
+
+    #[test]
+    fn limits_distance() {
+        let maze = Maze::new();
+        let explored = bfs_within([maze.find('S')], &maze, 2);
+        assert_eq!(explored.visited(), 5);
+        assert_eq!(explored.costs().values().max(), Some(&2));
+    }
+
+    #[test]
+    fn stops_at_goal() {
+        let maze = Maze::new();
+        let all = bfs([maze.find('S')], &maze);
+        let until = bfs_until([maze.find('S')], &maze, |&(row, _)| row == 4);
+        assert_eq!(until.goal(), Some(&(4, 0)));
+        assert_eq!(until.goal_cost(), Some(4));
+        assert!(until.expanded() < all.expanded());
+    }
+
+    #[test]
+    fn fills_reachable_states() {
+        let maze = Maze::new();
+        let open = MAZE.chars().filter(|c| *c != '#' && *c != '\n').count();
+        assert_eq!(flood_fill([maze.find('S')], &maze).len(), open);
+        assert_eq!(bfs([maze.find('S')], &maze).visited(), open);
+    }
+
+    #[test]
+    fn weighs_edges() {
+        // Walking into a column costs its index + 1, so detours along column 0 are cheap.
+        let maze = Maze::new();
+        let weighted = |position: &(usize, usize)| {
+            maze.neighbours(position)
+                .into_iter()
+                .map(|next| (next, next.1 + 1))
+                .collect::<Vec<_>>()
+        };
+        let (start, end) = (maze.find('S'), maze.find('E'));
+        let distance = |&(row, col): &(usize, usize)| end.0.abs_diff(row) + end.1.abs_diff(col);
+
+        let by_dijkstra = dijkstra([start], &weighted, |p| *p == end);
+        let by_astar = astar([start], &weighted, distance, |p| *p == end);
+        assert_eq!(by_dijkstra.goal_cost(), Some(83));
+        assert_eq!(by_astar.goal_cost(), Some(83));
+        assert_eq!(by_astar.goal_path(), by_dijkstra.goal_path());
+        assert!(by_astar.expanded() <= by_dijkstra.expanded());
+    }
+
+    #[test]
+    fn handles_zero_weights() {
+        let explored = dijkstra([0u32], &|n: &u32| [(n + 1, 0), (n + 2, 1)], |n| *n == 7);
+        assert_eq!(explored.goal_cost(), Some(0));
+    }
+
+    #[test]
+    fn reports_unreachable_goals() {
+        let explored = dijkstra([0u32], &|n: &u32| (*n < 5).then_some((n + 1, 1)), |n| {
+            *n == 7
+        });
+        assert_eq!(explored.goal(), None);
+        assert_eq!(explored.visited(), 6);
+        assert_eq!(explored.goal_path(), None);
+    }
+}
@@ -0,0 +1,383 @@
+use crate::linear::Rational;
+use num::{Num, PrimInt, Signed, Zero};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A point or direction in 3D space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Applies `f` to every component.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Vec3<U> {
+        Vec3::new(f(self.x), f(self.y), f(self.z))
+    }
+}
+
+impl<T: Num + Copy> Vec3<T> {
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl<T: Num + Copy + Signed> Vec3<T> {
+    pub fn manhattan(&self, other: &Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+impl<T: Num + Copy> Add for Vec3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Num + Copy> Sub for Vec3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Num + Copy> Mul<T> for Vec3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        self.map(|n| n * rhs)
+    }
+}
+
+/// Divides every component, truncating for integers.
+impl<T: Num + Copy> Div<T> for Vec3<T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        self.map(|n| n / rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|n| -n)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T> From<[T; 3]> for Vec3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An axis-aligned box of integer points, both corners inclusive.
+///
+/// ```
+/// # use advent_of_code::space::{Aabb, Vec3};
+/// let brick = Aabb::new(Vec3::new(1, 0, 5), Vec3::new(1, 2, 5));
+/// let below = Aabb::new(Vec3::new(0, 0, 2), Vec3::new(2, 0, 2));
+/// assert!(!brick.overlaps(&below));
+/// assert!(brick.overlaps_xy(&below));
+/// assert_eq!(brick.at_z(3).min, Vec3::new(1, 0, 3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Aabb<T> {
+    pub min: Vec3<T>,
+    pub max: Vec3<T>,
+}
+
+impl<T: PrimInt> Aabb<T> {
+    /// Creates the box spanned by two opposite corners, in any order.
+    pub fn new(a: Vec3<T>, b: Vec3<T>) -> Self {
+        Self {
+            min: Vec3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Vec3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    /// The number of points along each axis.
+    pub fn size(&self) -> Vec3<T> {
+        Vec3::new(
+            self.max.x - self.min.x + T::one(),
+            self.max.y - self.min.y + T::one(),
+            self.max.z - self.min.z + T::one(),
+        )
+    }
+
+    pub fn volume(&self) -> T {
+        let size = self.size();
+        size.x * size.y * size.z
+    }
+
+    pub fn contains(&self, point: &Vec3<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.overlaps_xy(other) && overlap(self.min.z, self.max.z, other.min.z, other.max.z)
+    }
+
+    /// Whether the projections onto the `xy` plane overlap, i.e. one box is above the other.
+    pub fn overlaps_xy(&self, other: &Self) -> bool {
+        overlap(self.min.x, self.max.x, other.min.x, other.max.x)
+            && overlap(self.min.y, self.max.y, other.min.y, other.max.y)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Vec3::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Vec3::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+        match min.x <= max.x && min.y <= max.y && min.z <= max.z {
+            true => Some(Self { min, max }),
+            false => None,
+        }
+    }
+
+    /// The same box moved so that its lowest corner is at `min`.
+    pub fn at(&self, min: Vec3<T>) -> Self {
+        Self {
+            min,
+            max: Vec3::new(
+                min.x + (self.max.x - self.min.x),
+                min.y + (self.max.y - self.min.y),
+                min.z + (self.max.z - self.min.z),
+            ),
+        }
+    }
+
+    /// The same box moved vertically so that its bottom is at `z`.
+    pub fn at_z(&self, z: T) -> Self {
+        self.at(Vec3::new(self.min.x, self.min.y, z))
+    }
+}
+
+fn overlap<T: PrimInt>(start_a: T, end_a: T, start_b: T, end_b: T) -> bool {
+    start_a <= end_b && start_b <= end_a
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A line through `origin` along `direction`, parameterised as `origin + direction * t`.
+/// Intersections are computed as exact [`Rational`]s, so they never depend on `f64` rounding.
+///
+/// Treating only `t ≥ 0` as part of the line makes it a ray, e.g. the future path of a
+/// moving object with `t` as time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Line {
+    pub origin: Vec3<i128>,
+    pub direction: Vec3<i128>,
+}
+
+impl Line {
+    pub fn new(origin: Vec3<i128>, direction: Vec3<i128>) -> Self {
+        Self { origin, direction }
+    }
+
+    /// The point at an integer `t`.
+    pub fn position_at(&self, t: i128) -> Vec3<i128> {
+        self.origin + self.direction * t
+    }
+
+    /// The exact point at a fractional `t`.
+    pub fn point_at(&self, t: Rational) -> Vec3<Rational> {
+        self.origin.map(Rational::from_integer) + self.direction.map(Rational::from_integer) * t
+    }
+
+    /// Where the projections onto the `xy` plane cross, as `(t, s)` with
+    /// `self.point_at(t)` and `other.point_at(s)` sharing `x` and `y`.
+    /// Returns [`None`] for parallel projections.
+    ///
+    /// ```
+    /// # use advent_of_code::linear::Rational;
+    /// # use advent_of_code::space::{Line, Vec3};
+    /// let a = Line::new(Vec3::new(0, 0, 0), Vec3::new(2, 0, 0));
+    /// let b = Line::new(Vec3::new(3, -3, 9), Vec3::new(0, 2, 1));
+    /// let (t, s) = a.intersect_xy(&b).unwrap();
+    /// assert_eq!((t, s), (Rational::new(3, 2), Rational::new(3, 2)));
+    /// ```
+    pub fn intersect_xy(&self, other: &Self) -> Option<(Rational, Rational)> {
+        // Solves origin + direction * t = other.origin + other.direction * s by Cramer's rule.
+        let (d1, d2) = (self.direction, other.direction);
+        let denominator = d1.x * d2.y - d1.y * d2.x;
+        if denominator.is_zero() {
+            return None;
+        }
+        let offset = other.origin - self.origin;
+        let t = offset.x * d2.y - offset.y * d2.x;
+        let s = offset.x * d1.y - offset.y * d1.x;
+        Some((Rational::new(t, denominator), Rational::new(s, denominator)))
+    }
+
+    /// Where the two lines meet in 3D, as `(t, s)` like [`Line::intersect_xy`].
+    /// Returns [`None`] for parallel or skew lines.
+    pub fn intersect(&self, other: &Self) -> Option<(Rational, Rational)> {
+        let swizzled = |line: &Self| {
+            [
+                *line,
+                Line::new(yzx(line.origin), yzx(line.direction)),
+                Line::new(zxy(line.origin), zxy(line.direction)),
+            ]
+        };
+        let (t, s) = swizzled(self)
+            .iter()
+            .zip(swizzled(other).iter())
+            .find_map(|(a, b)| a.intersect_xy(b))?;
+        match self.point_at(t) == other.point_at(s) {
+            true => Some((t, s)),
+            false => None,
+        }
+    }
+}
+
+fn yzx<T>(v: Vec3<T>) -> Vec3<T> {
+    Vec3::new(v.y, v.z, v.x)
+}
+
+fn zxy<T>(v: Vec3<T>) -> Vec3<T> {
+    Vec3::new(v.z, v.x, v.y)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Aabb, Line, Vec3};
+    use crate::linear::Rational;
+
+    #[test]
+    fn computes_vector_arithmetic() {
+        let a = Vec3::new(1, 2, 3);
+        let b = Vec3::new(4, -5, 6);
+        assert_eq!(a + b, Vec3::new(5, -3, 9));
+        assert_eq!(a - b, Vec3::new(-3, 7, -3));
+        assert_eq!(a * 2, Vec3::new(2, 4, 6));
+        assert_eq!(-a, Vec3::new(-1, -2, -3));
+        assert_eq!(a.dot(&b), 12);
+        assert_eq!(a.cross(&b), Vec3::new(27, 6, -13));
+        assert_eq!(a.cross(&b).dot(&a), 0);
+        assert_eq!(a.manhattan(&b), 13);
+        assert_eq!(Vec3::from([7, 8, 9]) / 2, Vec3::new(3, 4, 4));
+    }
+
+    #[test]
+    fn measures_boxes() {
+        let aabb = Aabb::new(Vec3::new(2u32, 0, 5), Vec3::new(0, 0, 3));
+        assert_eq!(aabb.min, Vec3::new(0, 0, 3));
+        assert_eq!(aabb.size(), Vec3::new(3, 1, 3));
+        assert_eq!(aabb.volume(), 9);
+        assert!(aabb.contains(&Vec3::new(1, 0, 4)));
+        assert!(!aabb.contains(&Vec3::new(1, 1, 4)));
+    }
+
+    #[test]
+    fn intersects_boxes() {
+        let a = Aabb::new(Vec3::new(0, 0, 0), Vec3::new(4, 4, 4));
+        let b = Aabb::new(Vec3::new(4, 2, 2), Vec3::new(6, 6, 6));
+        let c = Aabb::new(Vec3::new(5, 0, 0), Vec3::new(6, 2, 1));
+        assert_eq!(
+            a.intersection(&b),
+            Some(Aabb::new(Vec3::new(4, 2, 2), Vec3::new(4, 4, 4)))
+        );
+        assert!(a.overlaps(&b));
+        assert_eq!(a.intersection(&c), None);
+        assert!(!a.overlaps(&c));
+        assert!(!b.overlaps(&c));
+        assert!(b.overlaps_xy(&c));
+    }
+
+    #[test]
+    fn moves_boxes() {
+        let aabb = Aabb::new(Vec3::new(1u32, 1, 8), Vec3::new(1, 1, 10));
+        let dropped = aabb.at_z(1);
+        assert_eq!(dropped, Aabb::new(Vec3::new(1, 1, 1), Vec3::new(1, 1, 3)));
+        assert_eq!(dropped.size(), aabb.size());
+    }
+
+    #[test]
+    fn intersects_projected_lines() {
+        let a = Line::new(Vec3::new(19, 13, 30), Vec3::new(-2, 1, -2));
+        let b = Line::new(Vec3::new(18, 19, 22), Vec3::new(-1, -1, -2));
+        let (t, s) = a.intersect_xy(&b).unwrap();
+        let (p, q) = (a.point_at(t), b.point_at(s));
+        assert_eq!((p.x, p.y), (q.x, q.y));
+        assert_eq!(p.x, Rational::new(43, 3));
+        assert_eq!(p.y, Rational::new(46, 3));
+
+        let parallel = Line::new(Vec3::new(0, 0, 0), Vec3::new(-4, 2, 0));
+        assert_eq!(a.intersect_xy(&parallel), None);
+    }
+
+    #[test]
+    fn reports_intersections_in_the_past() {
+        let a = Line::new(Vec3::new(19, 13, 30), Vec3::new(-2, 1, -2));
+        let c = Line::new(Vec3::new(20, 19, 15), Vec3::new(1, -5, -3));
+        let (t, s) = a.intersect_xy(&c).unwrap();
+        assert!(t < Rational::from_integer(0));
+        assert!(s > Rational::from_integer(0));
+    }
+
+    #[test]
+    fn intersects_lines() {
+        let a = Line::new(Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        let b = Line::new(Vec3::new(6, 0, 3), Vec3::new(-1, 1, 0));
+        assert_eq!(
+            a.intersect(&b),
+            Some((Rational::from_integer(3), Rational::from_integer(3)))
+        );
+
+        let skew = Line::new(Vec3::new(6, 0, 4), Vec3::new(-1, 1, 0));
+        assert_eq!(a.intersect(&skew), None);
+
+        // Parallel in the xy projection, so only the other projections find the crossing.
+        let vertical = Line::new(Vec3::new(2, 2, 7), Vec3::new(0, 0, 1));
+        assert_eq!(
+            a.intersect(&vertical),
+            Some((Rational::from_integer(2), Rational::from_integer(-5)))
+        );
+    }
+
+    #[test]
+    fn moves_along_lines() {
+        let line = Line::new(Vec3::new(1, 2, 3), Vec3::new(-1, 0, 2));
+        assert_eq!(line.position_at(3), Vec3::new(-2, 2, 9));
+        assert_eq!(
+            line.point_at(Rational::new(1, 2)).z,
+            Rational::from_integer(4)
+        );
+    }
+}
@@ -0,0 +1,356 @@
+/// Module that caches the output of solution binaries.
+/// Results are keyed by day, part and hashes of the solution source and the puzzle input,
+/// so unchanged days can be replayed without compiling or running them again.
+/// The source hash also covers the library shared by all solutions.
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::template::aoc_cli::get_input_path;
+use crate::template::commands::all::get_path_for_bin;
+use crate::template::config;
+use crate::Day;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    pub day: Day,
+    pub source_hash: u64,
+    pub input_hash: u64,
+    pub is_release: bool,
+    pub is_timed: bool,
+}
+
+impl CacheKey {
+    /// Creates the cache key for a day from the current solution and input files.
+    /// Returns [`None`] if either of them does not exist.
+    pub fn new(day: Day, is_release: bool, is_timed: bool) -> Option<Self> {
+        let mut source = fs::read(get_path_for_bin(day)).ok()?;
+        source.extend(library_sources(Path::new(&config::get().paths.bins)));
+        let input = fs::read(get_input_path(day)).ok()?;
+
+        Some(Self {
+            day,
+            source_hash: hash(&source),
+            input_hash: hash(&input),
+            is_release,
+            is_timed,
+        })
+    }
+
+    fn mode(&self) -> &'static str {
+        match (self.is_release, self.is_timed) {
+            (true, true) => "release,timed",
+            (true, false) => "release",
+            (false, true) => "debug,timed",
+            (false, false) => "debug",
+        }
+    }
+}
+
+/// One cached section of a solution's output, i.e. the parse timing or a part's answer and timing.
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    key: CacheKey,
+    part: String,
+    lines: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct AnswerCache {
+    entries: Vec<Entry>,
+}
+
+impl AnswerCache {
+    /// Loads the cache from disk. A missing or unreadable cache is treated as empty.
+    #[must_use]
+    pub fn load() -> Self {
+        let content = fs::read_to_string(&config::get().paths.cache).unwrap_or_default();
+        Self::from_content(&content)
+    }
+
+    fn from_content(content: &str) -> Self {
+        Self {
+            entries: content.lines().filter_map(parse_entry).collect(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), io::Error> {
+        let path = Path::new(&config::get().paths.cache);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_content())
+    }
+
+    fn to_content(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}\t{}\t{:016x}\t{:016x}\t{}\t{}\n",
+                    entry.key.day,
+                    entry.part,
+                    entry.key.source_hash,
+                    entry.key.input_hash,
+                    entry.key.mode(),
+                    escape(&entry.lines.join("\n"))
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the cached output lines of a solution if it was run for `key` before.
+    #[must_use]
+    pub fn get(&self, key: &CacheKey) -> Option<Vec<String>> {
+        let lines: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.key == *key)
+            .flat_map(|entry| entry.lines.clone())
+            .collect();
+
+        match lines.is_empty() {
+            true => None,
+            false => Some(lines),
+        }
+    }
+
+    /// Replaces the cached results for the day and mode of `key` with the sections found in `output`.
+    pub fn insert(&mut self, key: &CacheKey, output: &[String]) {
+        self.entries
+            .retain(|entry| entry.key.day != key.day || entry.key.mode() != key.mode());
+
+        for (part, lines) in split_sections(output) {
+            self.entries.push(Entry {
+                key: key.clone(),
+                part,
+                lines,
+            });
+        }
+    }
+}
+
+/// Groups output lines by the part they belong to.
+/// A section starts at a `Parse:` or `Part N:` line and includes following lines of multi-line answers.
+fn split_sections(output: &[String]) -> Vec<(String, Vec<String>)> {
+    let mut sections: Vec<(String, Vec<String>)> = vec![];
+
+    for line in output {
+        let visible = visible_text(line);
+        let part = visible
+            .split(':')
+            .next()
+            .filter(|label| *label == "Parse" || label.starts_with("Part "));
+
+        match part {
+            Some(part) => sections.push((part.to_string(), vec![line.clone()])),
+            None => {
+                if let Some((_, lines)) = sections.last_mut() {
+                    lines.push(line.clone());
+                }
+            }
+        }
+    }
+
+    sections
+}
+
+/// Strips ANSI escape codes and everything overwritten by a carriage return.
+fn visible_text(line: &str) -> String {
+    let last_write = line.rsplit('\r').next().unwrap_or(line);
+    let mut text = String::new();
+    let mut chars = last_write.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(char::is_ascii_alphabetic);
+        } else {
+            text.push(c);
+        }
+    }
+
+    text
+}
+
+fn parse_entry(line: &str) -> Option<Entry> {
+    let mut fields = line.split('\t');
+
+    let day = fields.next()?.parse().ok()?;
+    let part = fields.next()?.to_string();
+    let source_hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let input_hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let mode = fields.next()?;
+    let lines = unescape(fields.next()?).lines().map(String::from).collect();
+
+    Some(Entry {
+        key: CacheKey {
+            day,
+            source_hash,
+            input_hash,
+            is_release: mode.starts_with("release"),
+            is_timed: mode.ends_with("timed"),
+        },
+        part,
+        lines,
+    })
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+
+    out
+}
+
+/// The paths and contents of the library sources next to the `bins` directory, e.g. `src/lib.rs`
+/// and the modules below `src`, in a stable order, followed by the `Cargo.toml` and `Cargo.lock`
+/// above them, which pick the dependencies. The solution binaries themselves are left out.
+fn library_sources(bins: &Path) -> Vec<u8> {
+    fn collect(dir: &Path, bins: &Path, files: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+            if path.is_dir() && path != bins {
+                collect(&path, bins, files);
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut files = vec![];
+    if let Some(src) = bins.parent() {
+        collect(src, bins, &mut files);
+        files.sort();
+        let root = src.parent().unwrap_or(Path::new(""));
+        files.extend(["Cargo.toml", "Cargo.lock"].map(|manifest| root.join(manifest)));
+    }
+
+    let mut sources = vec![];
+    for file in files {
+        sources.extend(file.to_string_lossy().as_bytes());
+        sources.extend(fs::read(&file).unwrap_or_default());
+    }
+    sources
+}
+
+/// 64-bit FNV-1a. Unlike the std hasher, its output is stable across Rust versions.
+fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{escape, hash, library_sources, split_sections, unescape, AnswerCache, CacheKey};
+    use crate::day;
+    use std::fs;
+
+    fn key(source_hash: u64) -> CacheKey {
+        CacheKey {
+            day: day!(5),
+            source_hash,
+            input_hash: 2,
+            is_release: true,
+            is_timed: false,
+        }
+    }
+
+    fn output() -> Vec<String> {
+        vec![
+            "debug output".into(),
+            "Parse: ✔\rParse: ✔ (2.9µs)".into(),
+            "Part 1: \x1b[1m35\x1b[0m\rPart 1: \x1b[1m35\x1b[0m (567.0ns)".into(),
+            "Part 2: ▼ \rPart 2: ▼  (1.1µs)".into(),
+            "#.#".into(),
+            "..#".into(),
+        ]
+    }
+
+    #[test]
+    fn splits_sections() {
+        let sections = split_sections(&output());
+        let parts: Vec<&str> = sections.iter().map(|(part, _)| part.as_str()).collect();
+        assert_eq!(parts, vec!["Parse", "Part 1", "Part 2"]);
+        assert_eq!(sections[2].1.len(), 3);
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let mut cache = AnswerCache::default();
+        cache.insert(&key(1), &output());
+
+        let loaded = AnswerCache::from_content(&cache.to_content());
+        assert_eq!(loaded.get(&key(1)), Some(output()[1..].to_vec()));
+        assert_eq!(loaded.get(&key(3)), None);
+    }
+
+    #[test]
+    fn replaces_stale_entries() {
+        let mut cache = AnswerCache::default();
+        cache.insert(&key(1), &output());
+        cache.insert(&key(3), &output());
+        assert_eq!(cache.get(&key(1)), None);
+        assert_eq!(cache.entries.len(), 3);
+    }
+
+    #[test]
+    fn escapes_control_characters() {
+        let s = "a\tb\\c\rd\ne";
+        assert_eq!(unescape(&escape(s)), s);
+        assert!(!escape(s).contains(['\t', '\n', '\r']));
+    }
+
+    #[test]
+    fn hashes_library_sources() {
+        let root = std::env::temp_dir().join(format!("answer-cache-{}", std::process::id()));
+        let bins = root.join("src/bin");
+        fs::create_dir_all(&bins).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod search;").unwrap();
+        fs::write(root.join("src/search.rs"), "pub fn bfs() {}").unwrap();
+        fs::write(bins.join("01.rs"), "fn main() {}").unwrap();
+
+        let before = hash(&library_sources(&bins));
+        fs::write(bins.join("02.rs"), "fn main() {}").unwrap();
+        assert_eq!(hash(&library_sources(&bins)), before);
+        fs::write(root.join("src/search.rs"), "pub fn bfs() { todo!() }").unwrap();
+        let after = hash(&library_sources(&bins));
+        fs::write(root.join("Cargo.lock"), "version = 4").unwrap();
+        let locked = hash(&library_sources(&bins));
+        fs::write(root.join("Cargo.toml"), "[dependencies]").unwrap();
+        let manifest = hash(&library_sources(&bins));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_ne!(after, before);
+        assert_ne!(locked, after);
+        assert_ne!(manifest, locked);
+    }
+
+    #[test]
+    fn hashes_consistently() {
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
@@ -4,6 +4,7 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
+use crate::template::config;
 use crate::Day;
 
 #[derive(Debug)]
@@ -82,19 +83,16 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     call_aoc_cli(&args)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+pub fn get_input_path(day: Day) -> String {
+    config::get().input_path(day)
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+pub fn get_puzzle_path(day: Day) -> String {
+    config::get().puzzle_path(day)
 }
 
 fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+    config::get().year
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
@@ -1,13 +1,16 @@
 use std::io;
 
 use crate::template::{
+    answer_cache::{AnswerCache, CacheKey},
+    config,
     readme_benchmarks::{self, Timings},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 use crate::{all_days, Day};
 
-pub fn handle(is_release: bool, is_timed: bool) {
+pub fn handle(is_release: bool, is_timed: bool, is_cached: bool) {
     let mut timings: Vec<Timings> = vec![];
+    let mut cache = AnswerCache::load();
 
     all_days().for_each(|day| {
         if day > 1 {
@@ -17,7 +20,26 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let key = CacheKey::new(day, is_release, is_timed);
+        let cached = key
+            .as_ref()
+            .filter(|_| is_cached)
+            .and_then(|key| cache.get(key));
+
+        let output = match cached {
+            Some(output) => {
+                output.iter().for_each(|line| println!("{line}"));
+                println!("{ANSI_ITALIC}(cached){ANSI_RESET}");
+                output
+            }
+            None => {
+                let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+                if let Some(key) = &key {
+                    cache.insert(key, &output);
+                }
+                output
+            }
+        };
 
         if output.is_empty() {
             println!("Not solved.");
@@ -27,12 +49,16 @@ pub fn handle(is_release: bool, is_timed: bool) {
         }
     });
 
+    if cache.save().is_err() {
+        eprintln!("Failed to write answer cache.");
+    }
+
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
+        if is_release && config::get().readme.benchmarks {
             match readme_benchmarks::update(timings, total_millis) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
@@ -58,13 +84,15 @@ impl From<std::io::Error> for Error {
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    format!("./{}", config::get().bin_path(day))
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::config::Overrides;
+    use crate::template::readme_benchmarks;
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
@@ -87,12 +115,16 @@ mod child_commands {
             args.push("--release");
         }
 
+        // mirror `--time` flag and config overrides to child invocations.
+        args.push("--");
+
         if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        let overrides = Overrides::from_env().to_args();
+        args.extend(overrides.iter().map(String::as_str));
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -128,6 +160,7 @@ mod child_commands {
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
         let mut timings = super::Timings {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
@@ -149,7 +182,9 @@ mod child_commands {
                 Some((part, timing_str, nanos))
             })
             .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
+                if part.contains("Parse") {
+                    timings.parse = Some(timing_str.into());
+                } else if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
@@ -161,12 +196,7 @@ mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -176,12 +206,7 @@ mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = readme_benchmarks::parse_duration(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
@@ -236,6 +261,23 @@ mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn test_parse_timing() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: ✔ (1.5µs @ 10000 samples)".into(),
+                    "Part 1: 35 (500.0ns @ 10000 samples)".into(),
+                    "Part 2: 46 (2.0µs @ 10000 samples)".into(),
+                    "".into(),
+                ],
+                day!(5),
+            );
+            assert_approx_eq!(res.total_nanos, 4000_f64);
+            assert_eq!(res.parse.unwrap(), "1.5µs");
+            assert_eq!(res.part_1.unwrap(), "500.0ns");
+            assert_eq!(res.part_2.unwrap(), "2.0µs");
+        }
+
         #[test]
         fn test_missing_parts() {
             let res = parse_exec_time(
@@ -3,3 +3,4 @@ pub mod download;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
@@ -1,9 +1,33 @@
-use std::process;
+use std::io::{IsTerminal, Write};
+use std::process::{self, Command, Stdio};
+use std::{env, fs, io};
 
-use crate::template::aoc_cli;
+use crate::template::{aoc_cli, markdown};
 use crate::Day;
 
-pub fn handle(day: Day) {
+pub fn handle(day: Day, part: Option<u8>) {
+    let puzzle_path = aoc_cli::get_puzzle_path(day);
+
+    // fall back to fetching the description if it has not been downloaded yet.
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        return read_online(day);
+    };
+
+    let puzzle = match part {
+        Some(part) => match markdown::select_part(&puzzle, part) {
+            Some(section) => section,
+            None => {
+                eprintln!("Part {part} is not available in \"{puzzle_path}\". Run `cargo download {day}` to refresh it.");
+                process::exit(1);
+            }
+        },
+        None => &puzzle,
+    };
+
+    page(&markdown::render(puzzle));
+}
+
+fn read_online(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -14,3 +38,33 @@ pub fn handle(day: Day) {
         process::exit(1);
     };
 }
+
+/// Pipes output through `$PAGER` (default: `less`) when writing to a terminal.
+/// Output is printed directly if stdout is redirected or the pager can not be spawned.
+fn page(output: &str) {
+    if io::stdout().is_terminal() {
+        let pager = env::var("PAGER").unwrap_or_else(|_| "less".into());
+        let mut parts = pager.split_whitespace();
+
+        if let Some(program) = parts.next() {
+            let mut cmd = Command::new(program);
+            cmd.args(parts).stdin(Stdio::piped());
+
+            // `-R` keeps ANSI styles, `-F` skips paging for output that fits the screen.
+            if program == "less" && env::var_os("LESS").is_none() {
+                cmd.env("LESS", "-RFX");
+            }
+
+            if let Ok(mut child) = cmd.spawn() {
+                if let Some(mut stdin) = child.stdin.take() {
+                    let _ = writeln!(stdin, "{output}");
+                }
+                let _ = child.wait();
+                return;
+            }
+        }
+    }
+
+    // ignore errors caused by closed pipes, e.g. when piping into `head`.
+    let _ = writeln!(io::stdout(), "{output}");
+}
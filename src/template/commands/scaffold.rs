@@ -4,6 +4,7 @@ use std::{
     process,
 };
 
+use crate::template::config;
 use crate::Day;
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
@@ -47,9 +48,10 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+    let config = config::get();
+    let input_path = config.input_path(day);
+    let example_path = config.example_path(&day.to_string());
+    let module_path = config.bin_path(day);
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
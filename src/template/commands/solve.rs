@@ -1,8 +1,9 @@
 use std::process::{Command, Stdio};
 
+use crate::template::config::Overrides;
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>, debug: Option<String>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if release {
@@ -20,6 +21,13 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    if let Some(debug) = debug {
+        cmd_args.push("--debug".to_string());
+        cmd_args.push(debug);
+    }
+
+    cmd_args.extend(Overrides::from_env().to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -0,0 +1,198 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::template::commands::all::get_path_for_bin;
+use crate::template::{aoc_cli, config, markdown, readme_benchmarks, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Day};
+
+static STARS_MARKER: &str = "<!--- advent_readme_stars table --->";
+
+struct DayStatus {
+    day: Day,
+    has_bin: bool,
+    has_input: bool,
+    examples: usize,
+    tests_pass: Option<bool>,
+    answers: Vec<String>,
+    stars: usize,
+    benchmark: Option<String>,
+}
+
+pub fn handle(is_release: bool) {
+    let readme = fs::read_to_string(&config::get().paths.readme).unwrap_or_default();
+    let timings = readme_benchmarks::parse_table(&readme).unwrap_or_default();
+    let stars = parse_stars(&readme);
+
+    let rows: Vec<DayStatus> = all_days()
+        .map(|day| {
+            let has_bin = Path::new(&get_path_for_bin(day)).exists();
+            let puzzle = fs::read_to_string(aoc_cli::get_puzzle_path(day)).unwrap_or_default();
+
+            DayStatus {
+                day,
+                has_bin,
+                has_input: fs::metadata(aoc_cli::get_input_path(day)).is_ok_and(|m| m.len() > 0),
+                examples: count_examples(day),
+                tests_pass: has_bin.then(|| run_tests(day, is_release)),
+                answers: markdown::puzzle_answers(&puzzle)
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                stars: stars
+                    .iter()
+                    .find(|(d, _)| *d == day)
+                    .map_or(0, |(_, count)| *count),
+                benchmark: timings.iter().find(|t| t.day == day).map(|t| {
+                    format!(
+                        "{} / {} / {}",
+                        t.parse.as_deref().unwrap_or("-"),
+                        t.part_1.as_deref().unwrap_or("-"),
+                        t.part_2.as_deref().unwrap_or("-")
+                    )
+                }),
+            }
+        })
+        .collect();
+
+    print_table(&rows);
+}
+
+fn print_table(rows: &[DayStatus]) {
+    let header = [
+        "Day",
+        "Bin",
+        "Input",
+        "Examples",
+        "Tests",
+        "Answers",
+        "Stars",
+        "Benchmark (parse / part 1 / part 2)",
+    ];
+
+    let cells: Vec<[String; 8]> = rows
+        .iter()
+        .map(|row| {
+            [
+                row.day.to_string(),
+                check(row.has_bin).into(),
+                check(row.has_input).into(),
+                row.examples.to_string(),
+                row.tests_pass.map_or("-", check).into(),
+                match row.answers.is_empty() {
+                    true => "-".into(),
+                    false => row.answers.join(", "),
+                },
+                "★".repeat(row.stars),
+                row.benchmark.clone().unwrap_or_else(|| "-".into()),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([header[i].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let format_row = |row: &[&str]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{ANSI_BOLD}{}{ANSI_RESET}", format_row(&header));
+    for row in &cells {
+        println!("{}", format_row(&row.each_ref().map(String::as_str)));
+    }
+
+    let total_stars: usize = rows.iter().map(|row| row.stars).sum();
+    println!("\n{ANSI_BOLD}Total stars:{ANSI_RESET} {total_stars}");
+}
+
+fn check(value: bool) -> &'static str {
+    match value {
+        true => "✓",
+        false => "✗",
+    }
+}
+
+/// Counts example files for a day, including variants such as `10a.txt` or `14-cycle-1.txt`.
+fn count_examples(day: Day) -> usize {
+    let prefix = day.to_string();
+    fs::read_dir(&config::get().paths.examples).map_or(0, |entries| {
+        entries
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.ends_with(".txt")
+                    && name.starts_with(&prefix)
+                    && !name[prefix.len()..].starts_with(|c: char| c.is_ascii_digit())
+                    && entry.metadata().is_ok_and(|m| m.len() > 0)
+            })
+            .count()
+    })
+}
+
+fn run_tests(day: Day, is_release: bool) -> bool {
+    let day_padded = day.to_string();
+    let mut args = vec!["test", "--quiet", "--bin", &day_padded];
+
+    if is_release {
+        args.push("--release");
+    }
+
+    Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Reads the number of stars per day from the table maintained by the readme stars workflow.
+fn parse_stars(readme: &str) -> Vec<(Day, usize)> {
+    let mut sections = readme.split(STARS_MARKER);
+    let table = sections.nth(1).unwrap_or_default();
+
+    table
+        .lines()
+        .filter_map(|line| {
+            let day = line
+                .strip_prefix("| [Day ")?
+                .split(']')
+                .next()?
+                .parse()
+                .ok()?;
+            Some((Day::new(day)?, line.matches('⭐').count()))
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_stars, STARS_MARKER};
+    use crate::day;
+
+    #[test]
+    fn parses_stars() {
+        let readme = format!(
+            "# readme\n{STARS_MARKER}\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |\n| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |\n{STARS_MARKER}\n"
+        );
+        assert_eq!(parse_stars(&readme), vec![(day!(1), 2), (day!(2), 1)]);
+    }
+
+    #[test]
+    fn parses_missing_stars_table() {
+        assert_eq!(parse_stars("# readme"), vec![]);
+    }
+}
@@ -0,0 +1,345 @@
+/// Project configuration read from `aoc.toml`.
+/// Every setting has a default, so the file and each of its sections are optional.
+/// Command-line flags take precedence over the file, see [`Overrides`].
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::{env, fs, process};
+
+use serde::Deserialize;
+
+use crate::Day;
+
+static DEFAULT_PATH: &str = "aoc.toml";
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The puzzle year passed to aoc-cli. Defaults to the current event when unset.
+    pub year: Option<u16>,
+    pub paths: Paths,
+    pub benchmark: Benchmark,
+    pub submit: Submit,
+    pub readme: Readme,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Paths {
+    pub inputs: String,
+    pub examples: String,
+    pub puzzles: String,
+    pub bins: String,
+    pub readme: String,
+    pub cache: String,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Self {
+            inputs: "data/inputs".into(),
+            examples: "data/examples".into(),
+            puzzles: "data/puzzles".into(),
+            bins: "src/bin".into(),
+            readme: "README.md".into(),
+            cache: "target/answer-cache.tsv".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Benchmark {
+    /// Approximate time spent benching each part with `--time`.
+    pub budget_ms: u64,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for Benchmark {
+    fn default() -> Self {
+        Self {
+            budget_ms: 1000,
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Submit {
+    /// Allows submitting answers with `--submit`.
+    pub enabled: bool,
+    /// Only submits answers computed by an optimized build.
+    pub require_release: bool,
+    /// Asks for confirmation before an answer is submitted.
+    pub confirm: bool,
+}
+
+impl Default for Submit {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            require_release: false,
+            confirm: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Readme {
+    /// Writes the benchmark table after `cargo all --release --time`.
+    pub benchmarks: bool,
+    /// Markdown heading prefix of the benchmark table.
+    pub heading: String,
+}
+
+impl Default for Readme {
+    fn default() -> Self {
+        Self {
+            benchmarks: true,
+            heading: "##".into(),
+        }
+    }
+}
+
+impl Config {
+    #[must_use]
+    pub fn input_path(&self, day: Day) -> String {
+        format!("{}/{day}.txt", self.paths.inputs)
+    }
+
+    #[must_use]
+    pub fn example_path(&self, example: &str) -> String {
+        format!("{}/{example}.txt", self.paths.examples)
+    }
+
+    #[must_use]
+    pub fn puzzle_path(&self, day: Day) -> String {
+        format!("{}/{day}.md", self.paths.puzzles)
+    }
+
+    #[must_use]
+    pub fn bin_path(&self, day: Day) -> String {
+        format!("{}/{day}.rs", self.paths.bins)
+    }
+
+    fn parse(content: &str) -> Result<Self, ConfigError> {
+        toml::from_str(content).map_err(|e| ConfigError::Parse(e.to_string()))
+    }
+
+    /// Rejects settings that parse but cannot be used, like an empty range of benchmark samples.
+    fn validate(&self) -> Result<(), ConfigError> {
+        let benchmark = &self.benchmark;
+        if benchmark.min_samples > benchmark.max_samples {
+            return Err(ConfigError::Invalid(format!(
+                "benchmark.min_samples ({}) is greater than benchmark.max_samples ({})",
+                benchmark.min_samples, benchmark.max_samples
+            )));
+        }
+        if benchmark.budget_ms == 0 {
+            return Err(ConfigError::Invalid(
+                "benchmark.budget_ms must be greater than 0".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Settings that can be overridden on the command-line of every command.
+/// The main binary mirrors them to the solution binaries it invokes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Overrides {
+    pub config: Option<String>,
+    pub year: Option<u16>,
+    pub inputs: Option<String>,
+    pub bench_budget_ms: Option<u64>,
+    pub no_readme: bool,
+}
+
+impl Overrides {
+    /// Consumes override flags from the passed arguments.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            config: args.opt_value_from_str("--config")?,
+            year: args.opt_value_from_str("--year")?,
+            inputs: args.opt_value_from_str("--inputs")?,
+            bench_budget_ms: args.opt_value_from_str("--bench-budget")?,
+            no_readme: args.contains("--no-readme"),
+        })
+    }
+
+    /// Reads override flags from the arguments of the current process, ignoring invalid values.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::parse(&mut pico_args::Arguments::from_env()).unwrap_or_default()
+    }
+
+    /// Formats the overrides as arguments for a child command.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(config) = &self.config {
+            args.extend(["--config".into(), config.clone()]);
+        }
+        if let Some(year) = self.year {
+            args.extend(["--year".into(), year.to_string()]);
+        }
+        if let Some(inputs) = &self.inputs {
+            args.extend(["--inputs".into(), inputs.clone()]);
+        }
+        if let Some(budget) = self.bench_budget_ms {
+            args.extend(["--bench-budget".into(), budget.to_string()]);
+        }
+        if self.no_readme {
+            args.push("--no-readme".into());
+        }
+        args
+    }
+
+    fn apply(&self, config: &mut Config) {
+        if let Some(year) = self.year {
+            config.year = Some(year);
+        }
+        if let Some(inputs) = &self.inputs {
+            config.paths.inputs.clone_from(inputs);
+        }
+        if let Some(budget) = self.bench_budget_ms {
+            config.benchmark.budget_ms = budget;
+        }
+        if self.no_readme {
+            config.readme.benchmarks = false;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    IO(String, std::io::Error),
+    Parse(String),
+    Invalid(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::IO(path, e) => write!(f, "could not read \"{path}\": {e}"),
+            ConfigError::Parse(e) => write!(f, "invalid configuration: {e}"),
+            ConfigError::Invalid(e) => write!(f, "invalid configuration: {e}"),
+        }
+    }
+}
+
+/// Loads the configuration and applies `overrides`.
+/// A missing `aoc.toml` yields the defaults, a missing file passed via `--config` is an error.
+/// The `AOC_YEAR` environment variable takes precedence over the file.
+pub fn load(overrides: &Overrides) -> Result<Config, ConfigError> {
+    let path = overrides.config.as_deref().unwrap_or(DEFAULT_PATH);
+
+    let mut config = match fs::read_to_string(path) {
+        Ok(content) => Config::parse(&content)?,
+        Err(e) if overrides.config.is_none() && e.kind() == std::io::ErrorKind::NotFound => {
+            Config::default()
+        }
+        Err(e) => return Err(ConfigError::IO(path.into(), e)),
+    };
+
+    if let Some(year) = env::var("AOC_YEAR").ok().and_then(|x| x.parse().ok()) {
+        config.year = Some(year);
+    }
+
+    overrides.apply(&mut config);
+    config.validate()?;
+    Ok(config)
+}
+
+/// Returns the configuration of the current process, loading it on first use.
+/// Exits the process if the configuration is invalid.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        load(&Overrides::from_env()).unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        })
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Config, Overrides};
+    use crate::day;
+
+    #[test]
+    fn uses_defaults_for_empty_file() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.year, None);
+        assert_eq!(config.input_path(day!(1)), "data/inputs/01.txt");
+        assert_eq!(config.bin_path(day!(12)), "src/bin/12.rs");
+        assert_eq!(config.benchmark.budget_ms, 1000);
+        assert_eq!(config.readme.heading, "##");
+        assert!(config.submit.enabled);
+    }
+
+    #[test]
+    fn parses_partial_sections() {
+        let config = Config::parse(
+            "year = 2022\n\n[paths]\ninputs = \"inputs\"\n\n[benchmark]\nmax_samples = 50\n",
+        )
+        .unwrap();
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(config.input_path(day!(3)), "inputs/03.txt");
+        assert_eq!(config.paths.examples, "data/examples");
+        assert_eq!(config.benchmark.max_samples, 50);
+        assert_eq!(config.benchmark.min_samples, 10);
+    }
+
+    #[test]
+    fn rejects_unusable_benchmark_settings() {
+        let config = Config::parse("[benchmark]\nmax_samples = 5\n").unwrap();
+        assert_eq!(
+            config.validate().unwrap_err().to_string(),
+            "invalid configuration: benchmark.min_samples (10) is greater than benchmark.max_samples (5)"
+        );
+        let config = Config::parse("[benchmark]\nbudget_ms = 0\n").unwrap();
+        assert!(config.validate().is_err());
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(Config::parse("[paths]\ninput = \"inputs\"\n").is_err());
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let mut config = Config::default();
+        let overrides = Overrides {
+            year: Some(2015),
+            inputs: Some("other".into()),
+            bench_budget_ms: Some(10),
+            no_readme: true,
+            ..Overrides::default()
+        };
+        overrides.apply(&mut config);
+        assert_eq!(config.year, Some(2015));
+        assert_eq!(config.paths.inputs, "other");
+        assert_eq!(config.benchmark.budget_ms, 10);
+        assert!(!config.readme.benchmarks);
+    }
+
+    #[test]
+    fn roundtrips_override_args() {
+        let overrides = Overrides {
+            config: Some("custom.toml".into()),
+            year: Some(2015),
+            inputs: None,
+            bench_budget_ms: Some(10),
+            no_readme: true,
+        };
+        let args = overrides.to_args().into_iter().map(Into::into).collect();
+        let parsed = Overrides::parse(&mut pico_args::Arguments::from_vec(args)).unwrap();
+        assert_eq!(parsed, overrides);
+    }
+}
@@ -0,0 +1,246 @@
+/// Renders the markdown puzzle descriptions written by aoc-cli to the terminal.
+/// Only the subset of markdown that aoc-cli emits is supported: headings, emphasis,
+/// inline code, code blocks, links and bullet lists.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+static PART_TWO_HEADING: &str = "\\--- Part Two ---";
+
+/// Returns the section of a puzzle description that belongs to `part`.
+/// Part one runs up to the part two heading, part two starts at it.
+#[must_use]
+pub fn select_part(markdown: &str, part: u8) -> Option<&str> {
+    let part_two_start = markdown
+        .match_indices(PART_TWO_HEADING)
+        .map(|(pos, _)| pos)
+        .find(|&pos| pos == 0 || markdown[..pos].ends_with('\n'));
+
+    match (part, part_two_start) {
+        (1, Some(pos)) => Some(&markdown[..pos]),
+        (1, None) => Some(markdown),
+        (2, Some(pos)) => Some(&markdown[pos..]),
+        _ => None,
+    }
+}
+
+/// Returns the answers recorded in a puzzle description, in part order.
+/// aoc-cli includes them when the description is downloaded after solving a part.
+#[must_use]
+pub fn puzzle_answers(markdown: &str) -> Vec<&str> {
+    markdown
+        .lines()
+        .filter_map(|line| line.strip_prefix("Your puzzle answer was `"))
+        .filter_map(|line| line.split('`').next())
+        .collect()
+}
+
+/// Renders a markdown puzzle description with ANSI styling.
+#[must_use]
+pub fn render(markdown: &str) -> String {
+    let mut lines: Vec<String> = vec![];
+    let mut in_code_block = false;
+    let mut source = markdown.lines().peekable();
+
+    while let Some(line) = source.next() {
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            lines.push(format!("    {line}"));
+            continue;
+        }
+
+        // headings are written as setext headings, i.e. underlined with dashes.
+        if source.peek().is_some_and(|next| is_heading_underline(next)) {
+            source.next();
+            let heading = line.strip_prefix('\\').unwrap_or(line);
+            lines.push(format!("{ANSI_BOLD}{heading}{ANSI_RESET}"));
+            continue;
+        }
+
+        match line.strip_prefix("* ") {
+            Some(item) => lines.push(format!("  • {}", render_inline(item))),
+            None => lines.push(render_inline(line)),
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn is_heading_underline(line: &str) -> bool {
+    line.len() >= 3 && line.chars().all(|c| c == '-')
+}
+
+#[derive(Default)]
+struct InlineStyle {
+    emphasis: bool,
+    code: bool,
+}
+
+impl InlineStyle {
+    /// Escape codes that restore this style after a reset.
+    fn ansi(&self) -> String {
+        let mut codes = String::new();
+        if self.emphasis {
+            codes.push_str(ANSI_BOLD);
+        }
+        if self.code {
+            codes.push_str(ANSI_ITALIC);
+        }
+        codes
+    }
+}
+
+/// Renders emphasis as bold (this is how the puzzle page highlights it), inline code
+/// as italic and links as their text followed by the target in parentheses.
+fn render_inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut style = InlineStyle::default();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                out.push(chars[i + 1]);
+                i += 2;
+                continue;
+            }
+            '`' => {
+                style.code = !style.code;
+                match style.code {
+                    true => out.push_str(ANSI_ITALIC),
+                    false => out.push_str(&format!("{ANSI_RESET}{}", style.ansi())),
+                }
+            }
+            '*' => {
+                style.emphasis = !style.emphasis;
+                match style.emphasis {
+                    true => out.push_str(ANSI_BOLD),
+                    false => out.push_str(&format!("{ANSI_RESET}{}", style.ansi())),
+                }
+            }
+            '[' if !style.code => {
+                if let Some((label, target, end)) = parse_link(&chars, i) {
+                    out.push_str(&render_inline(&label));
+                    out.push_str(&style.ansi());
+                    out.push_str(&format!(" ({ANSI_ITALIC}{target}{ANSI_RESET}"));
+                    out.push_str(&style.ansi());
+                    out.push(')');
+                    i = end;
+                    continue;
+                }
+                out.push('[');
+            }
+            c => out.push(c),
+        }
+        i += 1;
+    }
+
+    if style.emphasis || style.code {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+/// Parses a `[label](target)` link starting at `start`.
+/// Returns the label, the target and the index after the closing parenthesis.
+/// Labels may contain nested brackets and targets may contain balanced parentheses.
+fn parse_link(chars: &[char], start: usize) -> Option<(String, String, usize)> {
+    let label_end = find_closing(chars, start, '[', ']')?;
+    if chars.get(label_end + 1) != Some(&'(') {
+        return None;
+    }
+    let target_end = find_closing(chars, label_end + 1, '(', ')')?;
+
+    let label = chars[start + 1..label_end].iter().collect();
+    let target = chars[label_end + 2..target_end].iter().collect();
+    Some((label, target, target_end + 1))
+}
+
+fn find_closing(chars: &[char], start: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    for (i, &c) in chars.iter().enumerate().skip(start) {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_answers, render, render_inline, select_part};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    const PUZZLE: &str = "\\--- Day 1: Trebuchet?! ---\n----------\n\nSome *text*.\n\n```\n1abc2\n```\n\n\\--- Part Two ---\n----------\n\nMore text.";
+
+    #[test]
+    fn renders_headings() {
+        let rendered = render(PUZZLE);
+        assert!(rendered.starts_with(&format!(
+            "{ANSI_BOLD}--- Day 1: Trebuchet?! ---{ANSI_RESET}\n\n"
+        )));
+        assert!(!rendered.contains("----------"));
+    }
+
+    #[test]
+    fn renders_code_blocks() {
+        let rendered = render(PUZZLE);
+        assert!(rendered.contains("\n    1abc2\n"));
+        assert!(!rendered.contains("```"));
+    }
+
+    #[test]
+    fn renders_emphasis_and_code() {
+        assert_eq!(
+            render_inline("a *b* `c`"),
+            format!("a {ANSI_BOLD}b{ANSI_RESET} {ANSI_ITALIC}c{ANSI_RESET}")
+        );
+    }
+
+    #[test]
+    fn renders_nested_links() {
+        let rendered = render_inline("see [Joker](https://en.wikipedia.org/wiki/Joker_(card)).");
+        assert_eq!(
+            rendered,
+            format!(
+                "see Joker ({ANSI_ITALIC}https://en.wikipedia.org/wiki/Joker_(card){ANSI_RESET})."
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_unmatched_brackets() {
+        assert_eq!(render_inline("[Share [this]"), "[Share [this]");
+    }
+
+    #[test]
+    fn renders_bullets() {
+        assert_eq!(render("* one\n* two"), "  • one\n  • two");
+    }
+
+    #[test]
+    fn selects_parts() {
+        assert!(select_part(PUZZLE, 1).unwrap().ends_with("```\n\n"));
+        assert!(select_part(PUZZLE, 2)
+            .unwrap()
+            .starts_with("\\--- Part Two ---"));
+        assert_eq!(select_part("only part one", 2), None);
+        assert_eq!(select_part("only part one", 1), Some("only part one"));
+    }
+
+    #[test]
+    fn finds_answers() {
+        let puzzle = "text\n\nYour puzzle answer was `42`.\n\nYour puzzle answer was `abc`.";
+        assert_eq!(puzzle_answers(puzzle), vec!["42", "abc"]);
+        assert!(puzzle_answers(PUZZLE).is_empty());
+    }
+}
@@ -1,8 +1,11 @@
 use crate::Day;
 use std::{env, fs};
 
+pub mod answer_cache;
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
+pub mod markdown;
 pub mod readme_benchmarks;
 pub mod runner;
 
@@ -11,10 +14,16 @@ pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// The `inputs` and `examples` folders resolve to the directories configured in `aoc.toml`.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let config = config::get();
+    let filepath = match folder {
+        "inputs" => cwd.join(config.input_path(day)),
+        "examples" => cwd.join(config.example_path(&day.to_string())),
+        _ => cwd.join("data").join(folder).join(format!("{day}.txt")),
+    };
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -22,26 +31,66 @@ pub fn read_file(folder: &str, day: Day) -> String {
 // Helper function to read from examples
 pub fn read_example(example: &str) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join("examples")
-        .join(format!("{example}.txt"));
+    let filepath = cwd.join(config::get().example_path(example));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// Days that pass a `parse` function get it timed on its own, and both parts
+/// borrow its output instead of parsing the raw input themselves:
+///
+/// ```ignore
+/// advent_of_code::solution!(5, parse::read);
+///
+/// pub fn part_one(almanac: &Almanac) -> Option<i64> { ... }
+/// ```
+///
+/// Days that pass a `debug` function print its output instead of solving the puzzle when
+/// run with `--debug <request>`. It gets the raw input and the request, and returns an
+/// [`Answer`](runner::Answer) like the parts do:
+///
+/// ```ignore
+/// advent_of_code::solution!(20, debug = debug);
+///
+/// fn debug(input: &str, request: &str) -> Result<String, String> { ... }
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
+        advent_of_code::solution!($day, debug = no_debug);
+    };
+    ($day:expr, debug = $debug:expr) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
         fn main() {
             use advent_of_code::template::runner::*;
             let input = advent_of_code::template::read_file("inputs", DAY);
+            if let Some(request) = debug_request() {
+                return run_debug($debug, &input, &request);
+            }
             run_part(part_one, &input, DAY, 1);
             run_part(part_two, &input, DAY, 2);
         }
     };
+    ($day:expr, $parse:expr, debug = $debug:expr) => {
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+
+        fn main() {
+            use advent_of_code::template::runner::*;
+            let input = advent_of_code::template::read_file("inputs", DAY);
+            if let Some(request) = debug_request() {
+                return run_debug($debug, &input, &request);
+            }
+            let parsed = run_parse($parse, &input);
+            run_part(part_one, &parsed, DAY, 1);
+            run_part(part_two, &parsed, DAY, 2);
+        }
+    };
+    ($day:expr, $parse:expr) => {
+        advent_of_code::solution!($day, $parse, debug = no_debug);
+    };
 }
@@ -2,6 +2,8 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::commands::all::get_path_for_bin;
+use crate::template::config;
 use crate::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -21,6 +23,7 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
@@ -31,9 +34,19 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Parses a duration as formatted by [`std::time::Duration`]'s `Debug` impl into nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -65,16 +78,19 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
+            timing
+                .parse
+                .map_or_else(|| "-".into(), |parse| format!("`{parse}`")),
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
         ));
@@ -87,15 +103,57 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
     lines.join("\n")
 }
 
+/// Reads the timings recorded in the benchmark table of a readme.
+pub fn parse_table(readme: &str) -> Result<Vec<Timings>, Error> {
+    let positions = locate_table(readme)?;
+    let table = &readme[positions.pos_start..positions.pos_end];
+
+    Ok(table.lines().filter_map(parse_row).collect())
+}
+
+fn parse_row(line: &str) -> Option<Timings> {
+    let mut columns = line.strip_prefix("| [Day ")?.split('|');
+
+    let day = columns.next()?.split(']').next()?.parse().ok()?;
+    let day = Day::new(day)?;
+
+    let values: Vec<Option<String>> = columns
+        .map(|col| col.trim().trim_matches('`'))
+        .filter(|col| !col.is_empty())
+        .map(|col| (col != "-").then(|| col.to_string()))
+        .collect();
+
+    // tables written before parse times were recorded only have the two part columns.
+    let (parse, part_1, part_2) = match values.as_slice() {
+        [parse, part_1, part_2] => (parse.clone(), part_1.clone(), part_2.clone()),
+        [part_1, part_2] => (None, part_1.clone(), part_2.clone()),
+        _ => return None,
+    };
+
+    let total_nanos = [&parse, &part_1, &part_2]
+        .into_iter()
+        .flatten()
+        .filter_map(|x| parse_duration(x))
+        .sum();
+
+    Some(Timings {
+        day,
+        parse,
+        part_1,
+        part_2,
+        total_nanos,
+    })
+}
+
 fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table(&config::get().readme.heading, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
 pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
+    let path = &config::get().paths.readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     update_content(&mut readme, timings, total_millis)?;
     fs::write(path, &readme)?;
@@ -104,25 +162,28 @@ pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{parse_table, update_content, Timings, MARKER};
     use crate::day;
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
+                parse: None,
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
+                parse: None,
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
+                parse: Some("5ms".into()),
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
                 total_nanos: 9e+10,
@@ -170,11 +231,11 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | - | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | - | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `5ms` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +244,30 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn parses_benchmarks() {
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let timings = parse_table(&s).unwrap();
+        assert_eq!(timings.len(), 3);
+        assert_eq!(timings[0].parse, None);
+        assert_eq!(timings[2].day, day!(4));
+        assert_eq!(timings[2].parse.as_deref(), Some("5ms"));
+        assert_eq!(timings[2].part_1.as_deref(), Some("40ms"));
+        assert_eq!(timings[2].part_2.as_deref(), Some("50ms"));
+        assert_eq!(timings[2].total_nanos, 95_000_000_f64);
+    }
+
+    #[test]
+    fn parses_benchmarks_without_parse_column() {
+        let s = format!(
+            "{MARKER}\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---:  |\n| [Day 1](./src/bin/01.rs) | `10ms` | `-` |\n{MARKER}"
+        );
+        let timings = parse_table(&s).unwrap();
+        assert_eq!(timings.len(), 1);
+        assert_eq!(timings[0].parse, None);
+        assert_eq!(timings[0].part_1.as_deref(), Some("10ms"));
+        assert_eq!(timings[0].part_2, None);
+    }
 }
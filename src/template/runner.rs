@@ -1,30 +1,99 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, config, ANSI_ITALIC, ANSI_RESET};
 use crate::Day;
 use std::fmt::Display;
-use std::io::{stdout, Write};
+use std::io::{stdin, stdout, Write};
 use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// What a solution part returns: an [`Option`] when there may be no answer, or a [`Result`]
+/// whose error explains why there is none. The error is printed in place of the answer.
+pub trait Answer {
+    type Value: Display;
+
+    fn into_result(self) -> Result<Self::Value, Option<String>>;
+}
+
+impl<T: Display> Answer for Option<T> {
+    type Value = T;
+
+    fn into_result(self) -> Result<T, Option<String>> {
+        self.ok_or(None)
+    }
+}
+
+impl<T: Display, E: Display> Answer for Result<T, E> {
+    type Value = T;
+
+    fn into_result(self) -> Result<T, Option<String>> {
+        self.map_err(|error| Some(error.to_string()))
+    }
+}
+
+pub fn run_part<I: Clone, A: Answer>(func: impl Fn(I) -> A, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(
+        |input| func(input).into_result(),
+        input,
+        |result| print_result(result, &part_str, ""),
+    );
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
+    if let Ok(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// The request passed with `--debug`, if any, for days that print debug output.
+pub fn debug_request() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--debug")? + 1;
+
+    let Some(request) = args.get(index) else {
+        eprintln!("Unexpected command-line input. Format: cargo solve 1 --debug <request>");
+        process::exit(1);
+    };
+
+    Some(request.clone())
+}
+
+/// Print the output of a day's `debug` function for `request` instead of solving the puzzle.
+pub fn run_debug<A: Answer>(func: impl Fn(&str, &str) -> A, input: &str, request: &str) {
+    match func(input, request).into_result() {
+        Ok(output) => println!("{output}"),
+        Err(error) => {
+            let error = error.unwrap_or_else(|| format!("no debug output for {request:?}"));
+            eprintln!("Debug: ✖ {error}");
+            process::exit(1);
+        }
+    }
+}
+
+/// The `debug` function of days without debug output.
+pub fn no_debug(_input: &str, _request: &str) -> Result<String, &'static str> {
+    Err("this day has no debug output")
+}
+
+/// Parse the puzzle input for days that share a parsed input between both parts.
+/// The parse is timed and reported separately from the parts.
+pub fn run_parse<T>(func: impl Fn(&str) -> T, input: &str) -> T {
+    let (parsed, duration, samples) = run_timed(func, input, |_| print!("Parse: ✔"));
+
+    print!("\r");
+    println!("Parse: ✔{}", format_duration(&duration, samples));
+
+    parsed
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. the configured budget of execution time, by default 1 second,
+///     or the configured minimum of 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
@@ -51,8 +120,10 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let benchmark = &config::get().benchmark;
+    let bench_iterations = (Duration::from_millis(benchmark.budget_ms).as_nanos()
+        / cmp::max(base_time.as_nanos(), 10))
+    .clamp(benchmark.min_samples, benchmark.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -87,11 +158,11 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Result<T, Option<String>>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -111,20 +182,24 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Err(error) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
+                let error = error
+                    .as_ref()
+                    .map_or(String::new(), |error| format!(" {error}"));
                 print!("\r");
-                println!("{part}: ✖             ");
+                println!("{part}: ✖{error}             ");
             }
         }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
+///  1. submissions are enabled in the config (and we are in `--release` mode, if required).
 ///  2. aoc-cli is installed.
+///  3. the submission was confirmed, if required.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
@@ -152,6 +227,22 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    let policy = &config::get().submit;
+
+    if !policy.enabled {
+        eprintln!("Submitting is disabled in the config.");
+        return None;
+    }
+
+    if policy.require_release && cfg!(debug_assertions) {
+        eprintln!("Submitting requires an optimized build. Try running with \"--release\".");
+        return None;
+    }
+
+    if policy.confirm && !confirm_submit(&result, part) {
+        return None;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -160,3 +251,11 @@ fn submit_result<T: Display>(
     println!("Submitting result via aoc-cli...");
     Some(aoc_cli::submit(day, part, &result.to_string()))
 }
+
+fn confirm_submit<T: Display>(result: &T, part: u8) -> bool {
+    print!("Submit {ANSI_BOLD}{result}{ANSI_RESET} for part {part}? [y/N] ");
+    let _ = stdout().flush();
+
+    let mut answer = String::new();
+    stdin().read_line(&mut answer).is_ok() && answer.trim().eq_ignore_ascii_case("y")
+}